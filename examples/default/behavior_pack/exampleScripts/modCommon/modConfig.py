@@ -1,7 +1,7 @@
 ProjectName = "{{mod_name}}"
 
-ServerSystemName = "{{mod_name}}ServerSystem"
-ServerSystemPath = "{{mod_name_lower}}Scripts.modServer.serverSystem.{{mod_name}}ServerSystem"
+ServerSystemName = "{{python_module|pascal}}ServerSystem"
+ServerSystemPath = "{{python_module}}Scripts.modServer.serverSystem.{{python_module|pascal}}ServerSystem"
 
-ClientSystemName = "{{mod_name}}ClientSystem"
-ClientSystemPath = "{{mod_name_lower}}Scripts.modClient.clientSystem.{{mod_name}}ClientSystem"
\ No newline at end of file
+ClientSystemName = "{{python_module|pascal}}ClientSystem"
+ClientSystemPath = "{{python_module}}Scripts.modClient.clientSystem.{{python_module|pascal}}ClientSystem"
\ No newline at end of file
@@ -5,7 +5,7 @@ import mod.server.extraServerApi as serverApi
 
 
 @Mod.Binding(name="__mod_name__", version="0.0.1")
-class __mod_name__(object):
+class __python_module|pascal__(object):
 
 	def __init__(self):
 		print("===== init __mod_name__ mod =====")
@@ -13,7 +13,7 @@ class __mod_name__(object):
 	@Mod.InitServer()
 	def on_server_init(self):
 		print("===== init __mod_name__ server =====")
-		serverApi.RegisterSystem("__mod_name__", "__mod_name__ServerSystem", "__mod_name_lower__Scripts.modServer.serverSystem.__mod_name__ServerSystem")
+		serverApi.RegisterSystem("__mod_name__", "__python_module|pascal__ServerSystem", "__python_module__Scripts.modServer.serverSystem.__python_module|pascal__ServerSystem")
 
 	@Mod.DestroyServer()
 	def on_server_destroy(self):
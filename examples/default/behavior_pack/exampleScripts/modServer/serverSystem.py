@@ -10,9 +10,9 @@ namespace = serverApi.GetEngineNamespace()
 engineSystemName = serverApi.GetEngineSystemName()
 
 
-class __mod_name__ServerSystem(EasyModServerSystem):
+class __python_module|pascal__ServerSystem(EasyModServerSystem):
 
     def __init__(self, namespace, systemName):
-        super(__mod_name__ServerSystem, self).__init__(namespace, systemName)
-        print("===== __mod_name__ServerSystem init =====")
+        super(__python_module|pascal__ServerSystem, self).__init__(namespace, systemName)
+        print("===== __python_module|pascal__ServerSystem init =====")
 
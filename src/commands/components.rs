@@ -1,10 +1,10 @@
 use crate::commands::ComponentsArgs;
-use crate::entity;
+use crate::entity::project::{PackKind, Project};
 use crate::utils::file;
 use crate::error::Result;
 use serde_json::{json, to_string_pretty};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 const COMPONENT_3D_ITEM: &str = "3ditem";
 
@@ -28,7 +28,8 @@ fn run_components(args: &ComponentsArgs) -> Result<()> {
             args.geo.as_deref().unwrap_or("./model.geo.json"),
             args.texture.as_deref().unwrap_or("./texture.png"),
             identifier,
-            &project_path
+            &project_path,
+            &args.packs
         ),
         _ => Err(crate::error::CliError::NotFound(
             format!("组件 '{}' 不存在", args.component)
@@ -59,27 +60,31 @@ fn create_3dmodel(
     geo: &str,
     texture: &str,
     identifier: &str,
-    project_path: &PathBuf,
+    project_path: &Path,
+    selectors: &[String],
 ) -> Result<()> {
-    let project_info = entity::get_current_release_info(&project_path)?;
-    
-    let beh_path = project_path.join(format!(
-        "behavior_pack_{}",
-        project_info.behavior_identifier
-    ));
-    let res_path = project_path.join(format!(
-        "resource_pack_{}",
-        project_info.resource_identifier
-    ));
+    let project = Project::load(project_path)?;
+    
+    let mut beh_pack = project.behavior_pack()?;
+    let mut res_pack = project.resource_pack()?;
+    for selector in selectors {
+        let pack = project.find_pack(selector)?;
+        match pack.kind {
+            PackKind::Behavior => beh_pack = pack,
+            PackKind::Resource => res_pack = pack,
+        }
+    }
+    let beh_path = beh_pack.dir();
+    let res_path = res_pack.dir();
     
-    create_item_files(&beh_path, &res_path, identifier)?;
-    copy_assets(&res_path, geo, texture, identifier)?;
-    create_attachable_file(&res_path, identifier)?;
+    create_item_files(beh_path, res_path, identifier)?;
+    copy_assets(res_path, geo, texture, identifier)?;
+    create_attachable_file(res_path, identifier)?;
     
     Ok(())
 }
 
-fn create_item_files(beh_path: &PathBuf, res_path: &PathBuf, identifier: &str) -> Result<()> {
+fn create_item_files(beh_path: &Path, res_path: &Path, identifier: &str) -> Result<()> {
     let behavior_item = create_behavior_item_json(identifier);
     let resource_item = create_resource_item_json(identifier);
     
@@ -138,7 +143,7 @@ fn create_resource_item_json(identifier: &str) -> serde_json::Value {
 }
 
 fn copy_assets(
-    res_path: &PathBuf,
+    res_path: &Path,
     geo: &str,
     texture: &str,
     identifier: &str,
@@ -151,7 +156,7 @@ fn copy_assets(
     Ok(())
 }
 
-fn copy_texture(res_path: &PathBuf, texture: &str, f_identifier: &str) -> Result<()> {
+fn copy_texture(res_path: &Path, texture: &str, f_identifier: &str) -> Result<()> {
     let texture_dir = res_path.join("textures/models");
     fs::create_dir_all(&texture_dir)?;
     
@@ -162,7 +167,7 @@ fn copy_texture(res_path: &PathBuf, texture: &str, f_identifier: &str) -> Result
 }
 
 fn copy_geometry(
-    res_path: &PathBuf,
+    res_path: &Path,
     geo: &str,
     identifier: &str,
     f_identifier: &str,
@@ -182,7 +187,7 @@ fn copy_geometry(
     Ok(())
 }
 
-fn create_attachable_file(res_path: &PathBuf, identifier: &str) -> Result<()> {
+fn create_attachable_file(res_path: &Path, identifier: &str) -> Result<()> {
     let attachable_dir = res_path.join("attachables");
     fs::create_dir_all(&attachable_dir)?;
     
@@ -1,102 +1,321 @@
 use crate::{
     config::Config,
-    entity::project::ProjectInfo,
-    template::TemplateEngine,
-    utils::{file, http::HttpClient},
+    entity::{
+        descriptor::{self, DESCRIPTOR_FILE, PackEntry, ProjectDescriptor, ProjectSection, TemplateSection},
+        name::ProjectName,
+        project::{Project, ProjectInfo},
+    },
+    template::{BUILTIN_VARIABLES, TemplateEngine, cache::TemplateCache, hook::HookPolicy, source::TemplateSource},
+    utils::{file, prompt},
 };
-use std::{fs, path::PathBuf};
+use std::{collections::HashSet, fs, path::{Path, PathBuf}};
 
-use crate::commands::CreateArgs;
-use crate::error::Result;
-use crate::utils::git;
+use crate::commands::{CreateArgs, manifest};
+use crate::error::{CliError, Result};
 use uuid::Uuid;
 
-pub fn execute(args: &CreateArgs, temp_dir: &PathBuf) {
-    if let Err(e) = create_project(&args.name, args.target.as_deref(), temp_dir) {
+pub fn execute(args: &CreateArgs, temp_dir: &Path) {
+    if let Err(e) = create_project(args, temp_dir) {
         eprintln!("错误: {}", e);
         return;
     }
-    println!("成功: 项目已创建");
+    if args.dry_run {
+        println!("成功: 预览完成, 未写入任何文件");
+    } else {
+        println!("成功: 项目已创建");
+    }
 }
 
-fn create_project(name: &str, target: Option<&str>, temp_dir: &PathBuf) -> Result<()> {
-    let target = target.unwrap_or("default");
+fn create_project(args: &CreateArgs, temp_dir: &Path) -> Result<()> {
+    let name = ProjectName::parse(&args.name, args.namespace.as_deref())?;
+    let config = Config::load();
+    let source = TemplateSource::parse(args.target.as_deref(), args.template_ref.as_deref(), &config);
+    println!("模板来源: {}", source);
 
-    check_example_exists(target)?;
+    let project_dir = args
+        .dir
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(format!("./{}", name.pack_folder)));
+    // `--dir .` 等路径没有目录名, 转换为绝对路径后才能确定临时目录的位置
+    let project_dir = file::absolute_path(&project_dir)?;
+    check_project_dir(&project_dir, args.force)?;
 
-    let local_dir = PathBuf::from(format!("./{}", name));
-    fs::create_dir(&local_dir)?;
+    let cache = TemplateCache::new(config.template_cache_dir());
+    let template_dir = source.fetch(temp_dir, &cache)?;
+
+    let project_info = generate_project_info(name, args);
+    if args.dry_run {
+        let engine = prepare_engine(&template_dir, &project_info, args)?;
+        return print_plan(&engine, &template_dir, &project_dir);
+    }
 
-    let template_dir = clone_and_copy_template(target, temp_dir, &local_dir)?;
+    let engine = prepare_engine(&template_dir, &project_info, args)?;
+    check_conflicts(&engine, &template_dir, &project_dir)?;
 
-    initialize_project_with_template(&template_dir, &local_dir, name)?;
+    let staging = StagingDir::create(&project_dir)?;
+    file::copy_folder(&template_dir, staging.path())?;
+    engine.process_directory(staging.path())?;
+    write_descriptor(staging.path(), &project_info, &source)?;
+    link_manifests(staging.path())?;
+    staging.commit(&project_dir)?;
+    println!("项目目录: {}", project_dir.display());
+
+    // 生成后操作在最终目录中执行, 命令记录的绝对路径 (例如虚拟环境) 才不会指向已删除的临时目录
+    engine
+        .run_hooks(
+            &project_dir,
+            HookPolicy {
+                trust_template: args.trust_template,
+                no_input: args.no_input,
+            },
+        )
+        .map_err(|e| {
+            CliError::InvalidData(format!(
+                "项目已创建于 {}, 但生成后操作失败: {}",
+                project_dir.display(),
+                e
+            ))
+        })?;
 
     Ok(())
 }
 
-fn check_example_exists(target: &str) -> Result<()> {
-    let check_url = format!(
-        "https://api.github.com/repos/AiYo-Studio/emod-cli/contents/examples/{}",
-        target
-    );
+fn check_project_dir(project_dir: &Path, force: bool) -> Result<()> {
+    if !project_dir.exists() {
+        return Ok(());
+    }
+    if !project_dir.is_dir() {
+        return Err(CliError::InvalidInput(format!(
+            "{} 已存在且不是目录",
+            project_dir.display()
+        )));
+    }
+    if !force {
+        return Err(CliError::InvalidInput(format!(
+            "目录 {} 已存在, 使用 --force 在已有目录中创建",
+            project_dir.display()
+        )));
+    }
+    Ok(())
+}
 
-    let client = if cfg!(debug_assertions) {
-        HttpClient::new_with_proxy("http://127.0.0.1:1080")?
-    } else {
-        HttpClient::new()?
-    };
+/// `--force` 在已有目录中创建时, 在生成任何文件之前检查是否会覆盖已有文件
+fn check_conflicts(engine: &TemplateEngine, template_dir: &Path, project_dir: &Path) -> Result<()> {
+    if !project_dir.is_dir() {
+        return Ok(());
+    }
+    let plan = engine.plan(template_dir)?;
+    let conflicts: Vec<String> = plan
+        .files
+        .iter()
+        .map(String::as_str)
+        .chain(std::iter::once(DESCRIPTOR_FILE))
+        .filter(|file| project_dir.join(file).exists())
+        .map(str::to_string)
+        .collect();
+    conflict_error(project_dir, &conflicts)
+}
 
-    let resp = client.get(&check_url)?;
+fn conflict_error(project_dir: &Path, conflicts: &[String]) -> Result<()> {
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+    let listed: Vec<String> = conflicts.iter().take(10).cloned().collect();
+    Err(CliError::InvalidInput(format!(
+        "目录 {} 中已存在 {} 个冲突的文件: {}{}",
+        project_dir.display(),
+        conflicts.len(),
+        listed.join(", "),
+        if conflicts.len() > listed.len() { ", ..." } else { "" }
+    )))
+}
 
-    if !resp.status().is_success() {
-        return Err(crate::error::CliError::NotFound(format!(
-            "示例模板 '{}' 不存在",
-            target
-        )));
+/// 项目先在目标目录旁的临时目录中生成, 成功后再移动到目标位置,
+/// 失败时临时目录会在 drop 时被删除, 不会留下未初始化完成的项目
+struct StagingDir {
+    path: PathBuf,
+    committed: bool,
+}
+
+impl StagingDir {
+    fn create(project_dir: &Path) -> Result<Self> {
+        let parent = match project_dir.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let name = project_dir
+            .file_name()
+            .ok_or_else(|| CliError::InvalidInput(format!("无效的项目目录: {}", project_dir.display())))?
+            .to_string_lossy()
+            .to_string();
+
+        fs::create_dir_all(&parent)?;
+        let path = parent.join(format!(".{}.staging-{}", name, std::process::id()));
+        if path.exists() {
+            fs::remove_dir_all(&path)?;
+        }
+        fs::create_dir(&path)?;
+
+        Ok(Self {
+            path,
+            committed: false,
+        })
     }
 
-    Ok(())
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 目标目录不存在时直接重命名, 否则在确认没有冲突后合并进已有目录
+    fn commit(mut self, project_dir: &Path) -> Result<()> {
+        if project_dir.exists() {
+            conflict_error(project_dir, &file::find_conflicts(&self.path, project_dir)?)?;
+            file::move_merge(&self.path, project_dir)?;
+            fs::remove_dir_all(&self.path)?;
+        } else {
+            fs::rename(&self.path, project_dir)?;
+        }
+        self.committed = true;
+        Ok(())
+    }
+}
+
+impl Drop for StagingDir {
+    fn drop(&mut self) {
+        if !self.committed && self.path.exists() {
+            match fs::remove_dir_all(&self.path) {
+                Ok(()) => eprintln!("已回滚: 删除临时目录 {}", self.path.display()),
+                Err(e) => eprintln!("警告: 无法删除临时目录 {}: {}", self.path.display(), e),
+            }
+        }
+    }
 }
 
-fn clone_and_copy_template(
-    target: &str,
-    temp_dir: &PathBuf,
-    local_dir: &PathBuf,
-) -> Result<PathBuf> {
-    let _ = fs::remove_dir_all(format!("{}/tmp", temp_dir.display()));
+/// 打印 `--dry-run` 的预览: 变量、重命名、最终文件树以及会被改写的文件
+fn print_plan(engine: &TemplateEngine, template_dir: &Path, project_dir: &Path) -> Result<()> {
+    let plan = engine.plan(template_dir)?;
 
-    let config = Config::load();
-    let url = &config.repo_url;
-    git::clone_remote_project(url.to_string(), temp_dir)?;
+    println!("项目目录: {}", project_dir.display());
+    println!("变量:");
+    let mut variables: Vec<_> = engine.variables().iter().collect();
+    variables.sort();
+    for (key, value) in variables {
+        println!(" - {} = {}", key, value);
+    }
 
-    let target_dir = PathBuf::from(format!("{}/tmp/examples/{}", temp_dir.display(), target));
-    file::copy_folder(&target_dir, local_dir)?;
+    if !plan.removed.is_empty() {
+        println!("移除:");
+        for path in &plan.removed {
+            println!(" - {}", path);
+        }
+    }
+    if !plan.renames.is_empty() {
+        println!("重命名:");
+        for (from, to) in &plan.renames {
+            println!(" - {} -> {}", from, to);
+        }
+    }
+
+    let mut files = plan.files.clone();
+    files.push(DESCRIPTOR_FILE.to_string());
+    files.sort();
+
+    println!("文件树:");
+    let mut printed = HashSet::new();
+    for file in &files {
+        let parts: Vec<&str> = file.split('/').collect();
+        let (file_name, dirs) = parts.split_last().unwrap();
+        for depth in 0..dirs.len() {
+            if printed.insert(dirs[..=depth].join("/")) {
+                println!("  {}{}/", "  ".repeat(depth), dirs[depth]);
+            }
+        }
+        println!("  {}{}", "  ".repeat(dirs.len()), file_name);
+    }
 
-    Ok(target_dir)
+    println!("将被改写的文件:");
+    for (path, placeholders) in &plan.rewrites {
+        if placeholders.is_empty() {
+            println!(" - {}", path);
+        } else {
+            println!(" - {}: {}", path, placeholders.join(", "));
+        }
+    }
+
+    if !plan.hooks.is_empty() {
+        println!("生成后操作:");
+        for hook in &plan.hooks {
+            println!(" - {}", hook);
+        }
+    }
+    Ok(())
 }
 
-fn initialize_project_with_template(
-    template_dir: &PathBuf,
-    local_dir: &PathBuf,
-    name: &str,
-) -> Result<()> {
-    let lower_name = format!(
-        "{}{}",
-        name.chars().next().unwrap().to_lowercase(),
-        &name[1..]
-    );
+/// 记录项目名称、模板来源以及生成后各个包所在的目录
+fn write_descriptor(project_dir: &Path, project_info: &ProjectInfo, source: &TemplateSource) -> Result<()> {
+    let pack_entry = |uuid: &str| -> Result<Option<PackEntry>> {
+        Ok(descriptor::find_pack_dir(project_dir, uuid)?.map(|dir| PackEntry {
+            uuid: uuid.to_string(),
+            dir,
+        }))
+    };
+    let descriptor = ProjectDescriptor {
+        project: ProjectSection {
+            name: project_info.name.display.clone(),
+            namespace: project_info.name.namespace.clone(),
+            python_module: project_info.name.python_module.clone(),
+        },
+        template: Some(TemplateSection {
+            source: source.location(),
+            git_ref: source.git_ref().map(|s| s.to_string()),
+        }),
+        behavior_packs: pack_entry(&project_info.behavior_pack_uuid)?.into_iter().collect(),
+        resource_packs: pack_entry(&project_info.resource_pack_uuid)?.into_iter().collect(),
+        package: Default::default(),
+    };
+    descriptor.save(project_dir)
+}
 
-    println!("项目名称: {}", name);
-    println!("标识名称: {}", lower_name);
+/// 让生成的行为包和资源包互相声明依赖, 模板不是标准的项目结构时跳过
+fn link_manifests(project_dir: &Path) -> Result<()> {
+    match Project::load(project_dir) {
+        Ok(project) => manifest::link_pairs(&project.pairs()),
+        Err(e) => {
+            eprintln!("警告: 无法读取生成的包, 跳过清单依赖: {}", e);
+            Ok(())
+        }
+    }
+}
 
-    let project_info = generate_project_info(name, &lower_name);
+/// 设置模板变量, 缺少的必需变量会被询问
+fn prepare_engine(
+    template_dir: &Path,
+    project_info: &ProjectInfo,
+    args: &CreateArgs,
+) -> Result<TemplateEngine> {
+    println!("项目名称: {}", project_info.name.display);
+    println!("标识名称: {}", project_info.name.python_module);
+    println!("命名空间: {}", project_info.name.namespace);
 
     let mut engine = TemplateEngine::load(template_dir)?;
 
-    engine.set_variable("mod_name".to_string(), project_info.name.clone());
+    engine.set_variable("mod_name".to_string(), project_info.name.display.clone());
     engine.set_variable(
         "mod_name_lower".to_string(),
-        project_info.lower_name.clone(),
+        project_info.name.python_module.clone(),
+    );
+    engine.set_variable(
+        "python_module".to_string(),
+        project_info.name.python_module.clone(),
+    );
+    engine.set_variable(
+        "namespace".to_string(),
+        project_info.name.namespace.clone(),
+    );
+    engine.set_variable(
+        "pack_name".to_string(),
+        project_info.name.pack_folder.clone(),
     );
     engine.set_variable(
         "behavior_pack_uuid".to_string(),
@@ -123,18 +342,80 @@ fn initialize_project_with_template(
         project_info.resource_pack_uuid.chars().take(8).collect(),
     );
 
-    engine.process_directory(local_dir)?;
+    // 内置变量同时记录在 emod.toml 和清单中, 不允许通过 --var 覆盖, 以免与项目信息不一致
+    for (key, value) in &args.vars {
+        if BUILTIN_VARIABLES.contains(&key.as_str()) {
+            let hint = match key.as_str() {
+                "mod_name" | "mod_name_lower" | "python_module" | "pack_name" => "请通过 --name 指定",
+                "namespace" => "请通过 --namespace 指定",
+                _ => "UUID 由 emod-cli 生成, 可使用 --seed 得到确定的 UUID",
+            };
+            return Err(CliError::InvalidInput(format!(
+                "'{}' 是内置变量, 不能通过 --var 设置, {}",
+                key, hint
+            )));
+        }
+        engine.set_variable(key.clone(), value.clone());
+    }
+    engine.apply_defaults();
+    resolve_missing_variables(&mut engine, args.no_input)?;
+    engine.apply_defaults();
+
+    Ok(engine)
+}
+
+/// 交互式询问模板中仍缺少的必需变量, `--no-input` 时直接报错
+fn resolve_missing_variables(engine: &mut TemplateEngine, no_input: bool) -> Result<()> {
+    let missing = engine.missing_variables();
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    if no_input {
+        let names: Vec<String> = missing
+            .iter()
+            .map(|(key, var_config)| format!("{} ({})", key, var_config.description))
+            .collect();
+        return Err(CliError::InvalidInput(format!(
+            "缺少必需的变量: {}, 请使用 --var <KEY=VALUE> 指定",
+            names.join(", ")
+        )));
+    }
+
+    for (key, var_config) in missing {
+        let value = loop {
+            let value = prompt::input_required(&var_config.prompt(&key))?;
+            match var_config.check(&key, &value).first() {
+                Some(error) => eprintln!("{}", error),
+                None => break value,
+            }
+        };
+        engine.set_variable(key, value);
+    }
 
     Ok(())
 }
 
-fn generate_project_info(name: &str, lower_name: &str) -> ProjectInfo {
+/// emod-cli 的 UUIDv5 命名空间, 即 `uuid5(NAMESPACE_URL, "https://github.com/AiYo-Studio/emod-cli")`
+const UUID_NAMESPACE: Uuid = uuid::uuid!("67aef1f4-2a60-562d-86a0-1aa5d529a8ca");
+
+/// 指定 `--seed` 或 `--deterministic` 时所有 UUID 由种子和用途按 UUIDv5 派生,
+/// 相同输入总是生成相同的项目
+fn generate_project_info(name: ProjectName, args: &CreateArgs) -> ProjectInfo {
+    let seed = match (&args.seed, args.deterministic) {
+        (Some(seed), _) => Some(seed.clone()),
+        (None, true) => Some(name.display.clone()),
+        (None, false) => None,
+    };
+    let uuid_for = |role: &str| match &seed {
+        Some(seed) => Uuid::new_v5(&UUID_NAMESPACE, format!("{}/{}", seed, role).as_bytes()),
+        None => Uuid::new_v4(),
+    };
     ProjectInfo {
-        name: name.to_string(),
-        lower_name: lower_name.to_string(),
-        behavior_pack_uuid: Uuid::new_v4().to_string(),
-        resource_pack_uuid: Uuid::new_v4().to_string(),
-        behavior_module_uuid: Uuid::new_v4().to_string(),
-        resource_module_uuid: Uuid::new_v4().to_string(),
+        name,
+        behavior_pack_uuid: uuid_for("behavior_pack").to_string(),
+        resource_pack_uuid: uuid_for("resource_pack").to_string(),
+        behavior_module_uuid: uuid_for("behavior_module").to_string(),
+        resource_module_uuid: uuid_for("resource_module").to_string(),
     }
 }
@@ -0,0 +1,185 @@
+use crate::commands::InfoArgs;
+use crate::entity::project::{Pack, PackKind, Project};
+use crate::error::Result;
+use crate::utils::file;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TEXTURE_EXTENSIONS: &[&str] = &["png", "tga", "jpg", "jpeg"];
+
+pub fn execute(args: &InfoArgs) {
+    if let Err(e) = run_info(args) {
+        eprintln!("错误: {}", e);
+    }
+}
+
+/// 按 `default` 模板的目录结构统计的资源数量
+#[derive(Debug, Default)]
+struct ContentCounts {
+    items: usize,
+    attachables: usize,
+    blocks: usize,
+    entities: usize,
+    textures: usize,
+    geometries: usize,
+    recipes: usize,
+    /// 语言文件名 (不含扩展名) 到键数量
+    lang_keys: BTreeMap<String, usize>,
+}
+
+fn run_info(args: &InfoArgs) -> Result<()> {
+    let project_dir = file::find_project_dir(&args.path)?;
+    let project = Project::load(&project_dir)?;
+    let counts = count_contents(&project)?;
+    let scripts_package = project
+        .scripts_root()
+        .and_then(|dir| dir.file_name())
+        .map(|name| name.to_string_lossy().to_string());
+
+    if args.json {
+        let packs: Vec<Value> = project
+            .packs()
+            .map(|pack| {
+                json!({
+                    "kind": match pack.kind {
+                        PackKind::Behavior => "behavior",
+                        PackKind::Resource => "resource",
+                    },
+                    "dir": pack.dir_name(),
+                    "uuid": pack.uuid,
+                    "version": pack.version,
+                    "min_engine_version": pack.min_engine_version(),
+                })
+            })
+            .collect();
+        let overview = json!({
+            "name": project.name(),
+            "root": project.root().display().to_string(),
+            "scripts_package": scripts_package,
+            "packs": packs,
+            "counts": {
+                "items": counts.items,
+                "attachables": counts.attachables,
+                "blocks": counts.blocks,
+                "entities": counts.entities,
+                "textures": counts.textures,
+                "geometries": counts.geometries,
+                "recipes": counts.recipes,
+                "lang_keys": counts.lang_keys,
+            },
+        });
+        println!("{}", serde_json::to_string_pretty(&overview)?);
+        return Ok(());
+    }
+
+    println!("名称: {}", project.name());
+    println!("目录: {}", project.root().display());
+    println!("脚本包: {}", scripts_package.as_deref().unwrap_or("无"));
+    for pack in project.packs() {
+        println!("{}: {}", pack.kind, pack.dir_name());
+        println!("  UUID: {}", pack.uuid);
+        println!("  版本: {}", pack.version);
+        println!(
+            "  最低引擎版本: {}",
+            pack.min_engine_version()
+                .map_or_else(|| "未知".to_string(), |v| v.to_string())
+        );
+    }
+    println!("内容:");
+    println!(" - 物品: {}", counts.items);
+    println!(" - 附着物: {}", counts.attachables);
+    println!(" - 方块: {}", counts.blocks);
+    println!(" - 实体: {}", counts.entities);
+    println!(" - 贴图: {}", counts.textures);
+    println!(" - 模型: {}", counts.geometries);
+    println!(" - 配方: {}", counts.recipes);
+    if counts.lang_keys.is_empty() {
+        println!(" - 语言键: 0");
+    } else {
+        let langs: Vec<String> = counts
+            .lang_keys
+            .iter()
+            .map(|(lang, count)| format!("{} {}", lang, count))
+            .collect();
+        println!(" - 语言键: {}", langs.join(", "));
+    }
+    Ok(())
+}
+
+fn count_contents(project: &Project) -> Result<ContentCounts> {
+    let mut counts = ContentCounts::default();
+    for pack in project.packs() {
+        match pack.kind {
+            PackKind::Behavior => count_behavior_pack(pack, &mut counts)?,
+            PackKind::Resource => count_resource_pack(pack, &mut counts)?,
+        }
+    }
+    Ok(counts)
+}
+
+fn count_behavior_pack(pack: &Pack, counts: &mut ContentCounts) -> Result<()> {
+    let dir = pack.dir();
+    counts.items += files_with_extensions(&dir.join("netease_items_beh"), &["json"])?.len()
+        + files_with_extensions(&dir.join("items"), &["json"])?.len();
+    counts.blocks += files_with_extensions(&dir.join("netease_blocks"), &["json"])?.len()
+        + files_with_extensions(&dir.join("blocks"), &["json"])?.len();
+    counts.entities += files_with_extensions(&dir.join("entities"), &["json"])?.len();
+    counts.recipes += files_with_extensions(&dir.join("recipes"), &["json"])?.len();
+    Ok(())
+}
+
+fn count_resource_pack(pack: &Pack, counts: &mut ContentCounts) -> Result<()> {
+    let dir = pack.dir();
+    counts.attachables += files_with_extensions(&dir.join("attachables"), &["json"])?.len();
+    counts.textures += files_with_extensions(&dir.join("textures"), TEXTURE_EXTENSIONS)?.len();
+
+    for path in files_with_extensions(&dir.join("models"), &["json"])? {
+        counts.geometries += count_geometries(&file::read_file_to_json(&path)?);
+    }
+
+    for path in files_with_extensions(&dir.join("texts"), &["lang"])? {
+        let lang = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let keys = fs::read_to_string(&path)?
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.starts_with('#') && line.contains('='))
+            .count();
+        *counts.lang_keys.entry(lang).or_default() += keys;
+    }
+    Ok(())
+}
+
+/// 同时支持 `minecraft:geometry` 数组格式和旧版 `geometry.xxx` 键格式
+fn count_geometries(json: &Value) -> usize {
+    if let Some(geometries) = json["minecraft:geometry"].as_array() {
+        return geometries.len();
+    }
+    json.as_object()
+        .map(|object| object.keys().filter(|key| key.starts_with("geometry.")).count())
+        .unwrap_or(0)
+}
+
+/// 递归列出目录中指定扩展名的文件, 目录不存在时返回空列表
+fn files_with_extensions(dir: &Path, extensions: &[&str]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if !dir.is_dir() {
+        return Ok(files);
+    }
+    for entry in walkdir::WalkDir::new(dir) {
+        let entry = entry?;
+        let matches = entry
+            .path()
+            .extension()
+            .and_then(|s| s.to_str())
+            .is_some_and(|ext| extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)));
+        if entry.file_type().is_file() && matches {
+            files.push(entry.into_path());
+        }
+    }
+    Ok(files)
+}
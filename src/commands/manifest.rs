@@ -0,0 +1,56 @@
+use crate::commands::{ManifestArgs, ManifestCommands, ManifestLinkArgs};
+use crate::entity::manifest;
+use crate::entity::project::{Pack, Project};
+use crate::error::{CliError, Result};
+use crate::utils::file;
+
+pub fn execute(args: &ManifestArgs) {
+    let result = match &args.command {
+        ManifestCommands::Link(args) => run_link(args),
+    };
+    if let Err(e) = result {
+        eprintln!("错误: {}", e);
+    }
+}
+
+fn run_link(args: &ManifestLinkArgs) -> Result<()> {
+    let project_dir = file::find_project_dir(&args.path)?;
+    let project = Project::load(&project_dir)?;
+
+    let pairs = match args.packs.as_slice() {
+        [] => project.pairs(),
+        [a, b] => {
+            let (a, b) = (project.find_pack(a)?, project.find_pack(b)?);
+            if a.kind == b.kind {
+                return Err(CliError::InvalidInput(format!(
+                    "{} 和 {} 都是{}, 需要指定一个行为包和一个资源包",
+                    a.dir_name(),
+                    b.dir_name(),
+                    a.kind
+                )));
+            }
+            vec![(a, b)]
+        }
+        _ => {
+            return Err(CliError::InvalidInput(
+                "--pack 需要指定一个行为包和一个资源包".to_string(),
+            ));
+        }
+    };
+    if pairs.is_empty() {
+        return Err(CliError::NotFound("可以配对的行为包和资源包".to_string()));
+    }
+
+    link_pairs(&pairs)?;
+    println!("成功: 清单依赖已更新");
+    Ok(())
+}
+
+/// 为每一对包互相添加依赖并打印结果
+pub fn link_pairs(pairs: &[(&Pack, &Pack)]) -> Result<()> {
+    for (a, b) in pairs {
+        let status = if manifest::link_packs(a, b)? { "已添加" } else { "已存在" };
+        println!(" - 依赖: {} <-> {} ({})", a.dir_name(), b.dir_name(), status);
+    }
+    Ok(())
+}
@@ -1,8 +1,11 @@
-use clap::{arg, Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 pub mod components;
 pub mod create;
+pub mod info;
+pub mod manifest;
 pub mod release;
+pub mod template;
 
 #[derive(Parser)]
 #[command(
@@ -26,6 +29,12 @@ pub enum Commands {
     Create(CreateArgs),
     /// Create a new component
     Components(ComponentsArgs),
+    /// Manage project templates
+    Template(TemplateArgs),
+    /// Edit the pack manifests of a project
+    Manifest(ManifestArgs),
+    /// Show an overview of the project
+    Info(InfoArgs),
 }
 
 #[derive(Args)]
@@ -33,9 +42,40 @@ pub struct ReleaseArgs {
     /// The path of the project
     #[arg(short, long)]
     pub path: Option<String>,
-    /// The version of the project
+    /// The version to release, in the form major.minor.patch
     #[arg(short, long)]
     pub ver: Option<String>,
+    /// Only release the given pack (directory name or UUID), can be repeated
+    #[arg(long = "pack", value_name = "PACK")]
+    pub packs: Vec<String>,
+    /// Bump every released pack by the given level even if unchanged
+    #[arg(long, value_name = "LEVEL", conflicts_with = "ver")]
+    pub bump: Option<BumpLevel>,
+    /// Bump the behavior packs by the given level even if unchanged
+    #[arg(long, value_name = "LEVEL", conflicts_with = "ver")]
+    pub bump_behavior: Option<BumpLevel>,
+    /// Bump the resource packs by the given level even if unchanged
+    #[arg(long, value_name = "LEVEL", conflicts_with = "ver")]
+    pub bump_resource: Option<BumpLevel>,
+    /// Allow releasing a version lower than the current one
+    #[arg(long)]
+    pub allow_downgrade: bool,
+    /// Print the computed versions, changed files and zip entries without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+    /// List the files that would be included in the release zip
+    #[arg(long)]
+    pub list: bool,
+    /// Package the current state without bumping versions or touching any JSON
+    #[arg(long, conflicts_with_all = ["ver", "bump", "bump_behavior", "bump_resource"])]
+    pub no_bump: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum BumpLevel {
+    Major,
+    Minor,
+    Patch,
 }
 
 #[derive(Args)]
@@ -43,9 +83,51 @@ pub struct CreateArgs {
     /// The name of the mod
     #[arg(short, long)]
     pub name: String,
-    /// Example target, default example is 'default'
+    /// Template target: an example name, a local directory, a local git repository
+    /// (optionally `<repo>#<example>`) or a `.zip` archive. Defaults to 'default'
     #[arg(short, long)]
     pub target: Option<String>,
+    /// Git tag, branch or commit of the template repository to use
+    #[arg(long)]
+    pub template_ref: Option<String>,
+    /// Set a template variable, can be repeated
+    #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_key_value)]
+    pub vars: Vec<(String, String)>,
+    /// Fail instead of prompting for missing template variables
+    #[arg(long)]
+    pub no_input: bool,
+    /// Run shell commands declared by the template without asking
+    #[arg(long)]
+    pub trust_template: bool,
+    /// The project directory, defaults to `./<name>`
+    #[arg(long)]
+    pub dir: Option<String>,
+    /// Allow creating into an existing directory without conflicting files
+    #[arg(long)]
+    pub force: bool,
+    /// Namespace for identifiers, derived from the name by default
+    #[arg(long)]
+    pub namespace: Option<String>,
+    /// Derive pack and module UUIDs from the project name instead of random ones
+    #[arg(long)]
+    pub deterministic: bool,
+    /// Derive pack and module UUIDs from the given seed, implies --deterministic
+    #[arg(long)]
+    pub seed: Option<String>,
+    /// Print the files that would be generated without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+fn parse_key_value(s: &str) -> Result<(String, String), String> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("invalid KEY=VALUE: no `=` found in `{}`", s))?;
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(format!("invalid KEY=VALUE: empty key in `{}`", s));
+    }
+    Ok((key.to_string(), value.to_string()))
 }
 
 #[derive(Args)]
@@ -64,5 +146,105 @@ pub struct ComponentsArgs {
     pub texture: Option<String>,
     /// The item's identifier
     #[arg(short, long)]
-    pub identifier: Option<String>
+    pub identifier: Option<String>,
+    /// The behavior or resource pack to add the component to (directory name or UUID),
+    /// can be repeated. Defaults to the first pack of each kind
+    #[arg(long = "pack", value_name = "PACK")]
+    pub packs: Vec<String>,
+}
+
+
+#[derive(Args)]
+pub struct TemplateArgs {
+    #[command(subcommand)]
+    pub command: TemplateCommands,
+}
+
+#[derive(Subcommand)]
+pub enum TemplateCommands {
+    /// List templates available in the configured sources
+    List(TemplateListArgs),
+    /// Show the variables, renames and processing rules of a template
+    Info(TemplateInfoArgs),
+    /// Check a template directory for common authoring mistakes
+    Validate(TemplateValidateArgs),
+    /// Manage the local template cache
+    Cache(TemplateCacheArgs),
+}
+
+#[derive(Args)]
+pub struct TemplateValidateArgs {
+    /// The template directory containing template.toml
+    #[arg(default_value = ".")]
+    pub dir: String,
+}
+
+#[derive(Args)]
+pub struct TemplateListArgs {
+    /// Git tag, branch or commit of the template repository to list
+    #[arg(long)]
+    pub template_ref: Option<String>,
+}
+
+#[derive(Args)]
+pub struct TemplateInfoArgs {
+    /// Template target, accepts the same values as `create --target`
+    pub target: Option<String>,
+    /// Git tag, branch or commit of the template repository to use
+    #[arg(long)]
+    pub template_ref: Option<String>,
+}
+
+#[derive(Args)]
+pub struct TemplateCacheArgs {
+    #[command(subcommand)]
+    pub command: TemplateCacheCommands,
+}
+
+#[derive(Subcommand)]
+pub enum TemplateCacheCommands {
+    /// List cached template repositories
+    List,
+    /// Remove cached template repositories
+    Prune(TemplateCachePruneArgs),
+}
+
+#[derive(Args)]
+pub struct TemplateCachePruneArgs {
+    /// Only remove entries not updated for the given number of days
+    #[arg(long)]
+    pub older_than: Option<u64>,
+}
+
+#[derive(Args)]
+pub struct ManifestArgs {
+    #[command(subcommand)]
+    pub command: ManifestCommands,
+}
+
+#[derive(Subcommand)]
+pub enum ManifestCommands {
+    /// Declare dependencies between paired behavior and resource packs
+    Link(ManifestLinkArgs),
+}
+
+#[derive(Args)]
+pub struct ManifestLinkArgs {
+    /// The path of the project
+    #[arg(short, long)]
+    pub path: Option<String>,
+    /// Link the given behavior and resource pack (directory name or UUID) instead of
+    /// pairing packs by their order in world_*_packs.json
+    #[arg(long = "pack", value_name = "PACK")]
+    pub packs: Vec<String>,
+}
+
+#[derive(Args)]
+pub struct InfoArgs {
+    /// The path of the project
+    #[arg(short, long)]
+    pub path: Option<String>,
+    /// Print the overview as JSON
+    #[arg(long)]
+    pub json: bool,
 }
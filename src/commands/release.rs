@@ -4,197 +4,547 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use walkdir;
 use zip::write::SimpleFileOptions;
 
-use crate::commands::ReleaseArgs;
-use crate::utils::file;
-use crate::{entity, entity::project::ReleaseInfo};
-use crate::error::Result;
+use crate::commands::{BumpLevel, ReleaseArgs};
+use crate::entity::manifest;
+use crate::entity::project::{Pack, PackKind, Project};
+use crate::entity::version::Version;
+use crate::utils::{file, git};
+use crate::utils::glob::to_slash_path;
+use crate::utils::ignore::IgnoreRules;
+use crate::error::{CliError, Result};
+
+/// 记录每个包上次发布时的版本和内容指纹, 用于判断包是否发生变化
+const STATE_FILE: &str = ".emod-release.json";
 
 pub fn execute(args: &ReleaseArgs) {
     if let Err(e) = run_release(args) {
         eprintln!("❌ 组件打包失败: {}", e);
-        return;
+        // 以非零状态退出, 以便在 CI 中使用 `--dry-run` 和 `--no-bump`
+        std::process::exit(1);
+    }
+    if !args.dry_run && !args.list {
+        println!("🍀 组件打包完成");
     }
-    println!("🍀 组件打包完成");
 }
 
 fn run_release(args: &ReleaseArgs) -> Result<()> {
     let project_dir = file::find_project_dir(&args.path)?;
-    let release_info = entity::get_current_release_info(&project_dir)?;
-    
-    println!("🔖 当前行为包版本: {:?}", release_info.behavior_version);
-    println!("🔖 当前资源包版本: {:?}", release_info.resource_version);
+    let project = Project::load(&project_dir)?;
+    let packs = project.select_packs(&args.packs)?;
+    if packs.is_empty() {
+        return Err(CliError::NotFound(format!(
+            "{} 和 {} 中没有登记任何包",
+            PackKind::Behavior.world_packs_file(),
+            PackKind::Resource.world_packs_file()
+        )));
+    }
+
+    println!("🔖 项目: {}", project.name());
+    if let Some(scripts_root) = project.scripts_root() {
+        println!("🔖 脚本目录: {}", scripts_root.display());
+    }
+    for pack in &packs {
+        println!("🔖 当前{}版本 ({}): {}", pack.kind, pack.dir_name(), pack.version);
+    }
     
-    release(&args.ver, &project_dir, &release_info)?;
+    let rules = IgnoreRules::load(project.root(), project.package_ignore())?;
+    if args.list {
+        return print_zip_entries(project.root(), &packs, &rules);
+    }
+    release(args, &project, &packs, &rules)?;
     
     Ok(())
 }
 
+/// 每个包在各自的版本上递增, 压缩包以第一个递增的包的新版本命名,
+/// `--no-bump` 时不修改任何 JSON, 直接以当前版本打包
 fn release(
-    version: &Option<String>,
-    project_dir: &PathBuf,
-    release_info: &ReleaseInfo,
+    args: &ReleaseArgs,
+    project: &Project,
+    packs: &[&Pack],
+    rules: &IgnoreRules,
 ) -> Result<()> {
-    let new_version = calculate_version(version, &release_info.behavior_version)?;
-    let version_value = Value::Array(new_version.iter().map(|v| Value::from(*v)).collect());
+    let versions = if args.no_bump {
+        Vec::new()
+    } else {
+        let state = ReleaseState::load(project.root())?;
+        calculate_versions(args, project, packs, &state, rules)?
+    };
+    let version_str = match versions.first() {
+        Some((_, version)) => version.to_string(),
+        None => packs[0].version.to_string(),
+    };
+    let output_path = project.root().join(format!("release_{}.zip", version_str));
     
-    println!("📦 开始打包, 版本号: {:?}", &new_version);
+    if args.dry_run {
+        return print_plan(project, packs, &versions, &output_path, rules);
+    }
     
-    update_versions(&project_dir, &release_info, &version_value)?;
+    update_versions(project, &versions)?;
     
-    let version_str = format!("{}.{}.{}", new_version[0], new_version[1], new_version[2]);
-    let output_path = package_project(&project_dir, &release_info, &version_str)?;
+    println!("📦 开始打包, 版本号: {}", version_str);
+    package_project(project.root(), packs, &output_path, rules)?;
     
-    println!("📦 打包完成: {}", output_path.replace("\\", "/"));
+    if !args.no_bump {
+        ReleaseState::record(project.root(), packs, rules)?;
+    }
+    println!("📦 打包完成: {}", output_path.display().to_string().replace("\\", "/"));
     Ok(())
 }
 
-fn calculate_version(version: &Option<String>, current: &[u32]) -> Result<Vec<u32>> {
-    if let Some(ver_str) = version {
-        ver_str
-            .split(".")
-            .map(|s| s.parse::<u32>().map_err(|e| e.into()))
-            .collect()
-    } else {
-        Ok(vec![current[0], current[1], current[2] + 1])
+/// 未指定版本或递增级别时只递增自上次发布以来内容有变化的包,
+/// 以及清单中的依赖版本因此被同步的包
+fn calculate_versions<'a>(
+    args: &ReleaseArgs,
+    project: &Project,
+    packs: &[&'a Pack],
+    state: &ReleaseState,
+    rules: &IgnoreRules,
+) -> Result<Vec<(&'a Pack, Version)>> {
+    let target = args.ver.as_deref().map(str::parse::<Version>).transpose()?;
+    let mut versions = Vec::new();
+    let mut unchanged = Vec::new();
+    for pack in packs {
+        let level = match pack.kind {
+            PackKind::Behavior => args.bump_behavior,
+            PackKind::Resource => args.bump_resource,
+        }
+        .or(args.bump);
+        let new_version = match (target, level) {
+            (Some(target), _) => target,
+            (None, Some(level)) => bump_version(pack.version, level),
+            (None, None) if state.is_changed(project.root(), pack, rules)? => {
+                bump_version(pack.version, BumpLevel::Patch)
+            }
+            (None, None) => {
+                unchanged.push(*pack);
+                continue;
+            }
+        };
+        if new_version < pack.version && !args.allow_downgrade {
+            return Err(CliError::InvalidInput(format!(
+                "{} ({}) 的新版本 {} 低于当前版本 {}, 使用 --allow-downgrade 允许降级",
+                pack.kind,
+                pack.dir_name(),
+                new_version,
+                pack.version
+            )));
+        }
+        println!("📦 {} ({}) 版本号: {}", pack.kind, pack.dir_name(), new_version);
+        versions.push((*pack, new_version));
     }
+
+    // 清单内容因同步依赖版本而改变的包也必须递增版本, 否则游戏会沿用旧版本的缓存,
+    // 递增后又可能使依赖它的包发生变化, 因此重复直到没有新的包需要递增
+    loop {
+        let synced = unchanged
+            .iter()
+            .position(|pack| updated_manifest(pack, &versions).is_some_and(|(_, synced)| !synced.is_empty()));
+        let Some(index) = synced else {
+            break;
+        };
+        let pack = unchanged.remove(index);
+        let new_version = bump_version(pack.version, BumpLevel::Patch);
+        println!(
+            "📦 {} ({}) 版本号: {} (同步了依赖版本)",
+            pack.kind,
+            pack.dir_name(),
+            new_version
+        );
+        versions.push((pack, new_version));
+    }
+    for pack in unchanged {
+        println!(
+            "📦 {} ({}) 自上次发布以来没有变化, 保持版本 {}",
+            pack.kind,
+            pack.dir_name(),
+            pack.version
+        );
+    }
+    for pack in project.packs() {
+        let selected = packs.iter().any(|p| p.uuid == pack.uuid);
+        if !selected && updated_manifest(pack, &versions).is_some() {
+            println!(
+                "🔗 {} ({}) 未包含在本次发布中, 其清单中的依赖版本会被同步, 下次发布时将递增版本",
+                pack.kind,
+                pack.dir_name()
+            );
+        }
+    }
+    
+    if versions.is_empty() {
+        return Err(CliError::InvalidInput(
+            "自上次发布以来没有包发生变化, 使用 --bump 强制递增版本或 --no-bump 直接打包".to_string(),
+        ));
+    }
+    Ok(versions)
 }
 
-fn update_versions(
-    project_dir: &PathBuf,
-    release_info: &ReleaseInfo,
-    version: &Value,
+fn bump_version(current: Version, level: BumpLevel) -> Version {
+    match level {
+        BumpLevel::Major => current.bump_major(),
+        BumpLevel::Minor => current.bump_minor(),
+        BumpLevel::Patch => current.bump_patch(),
+    }
+}
+
+/// `--dry-run`: 打印将被修改的文件和压缩包中的条目, 不写入任何文件
+fn print_plan(
+    project: &Project,
+    packs: &[&Pack],
+    versions: &[(&Pack, Version)],
+    output_path: &Path,
+    rules: &IgnoreRules,
 ) -> Result<()> {
-    update_pack_json(&project_dir, &version)?;
-    update_manifest_json(&project_dir, &release_info, &version)?;
+    println!("📝 将被修改的文件:");
+    let packs_files: BTreeSet<&str> = versions
+        .iter()
+        .map(|(pack, _)| pack.kind.world_packs_file())
+        .collect();
+    for packs_file in packs_files {
+        println!(" - {}", packs_file);
+    }
+    for pack in project.packs() {
+        if let Some((_, synced)) = updated_manifest(pack, versions) {
+            let path = display_relative(project.root(), &pack.manifest_path());
+            if synced.is_empty() {
+                println!(" - {}", path);
+            } else {
+                let dependencies: Vec<String> = synced.iter().map(|(dep, _)| dep.dir_name()).collect();
+                println!(" - {} (同步依赖: {})", path, dependencies.join(", "));
+            }
+        }
+    }
+    if !versions.is_empty() {
+        println!(" - {}", STATE_FILE);
+    }
+    println!(
+        " - {} ({})",
+        display_relative(project.root(), output_path),
+        if output_path.exists() { "覆盖" } else { "新建" }
+    );
+    
+    print_zip_entries(project.root(), packs, rules)?;
+    println!("📝 预览完成, 未写入任何文件");
     Ok(())
 }
 
-fn update_pack_json(project_dir: &PathBuf, version: &Value) -> Result<()> {
-    let paths = vec![
-        project_dir.join("world_behavior_packs.json"),
-        project_dir.join("world_resource_packs.json"),
-    ];
-    
-    for path in paths {
-        file::update_json_file(&path, |json| {
-            json[0]["version"] = version.clone();
-            Ok(())
-        })?;
+/// `--list`: 打印压缩包中将包含的条目
+fn print_zip_entries(project_dir: &Path, packs: &[&Pack], rules: &IgnoreRules) -> Result<()> {
+    println!("📝 压缩包条目:");
+    let mut files = 0;
+    for pack in packs {
+        for entry in collect_zip_entries(project_dir, pack.dir(), rules)? {
+            match entry.source {
+                Some(_) => {
+                    files += 1;
+                    println!(" - {}", entry.name);
+                }
+                None => println!(" - {}/", entry.name),
+            }
+        }
     }
-    
+    println!("📝 共 {} 个文件", files);
     Ok(())
 }
 
-fn update_manifest_json(
-    project_dir: &PathBuf,
-    release_info: &ReleaseInfo,
-    version: &Value,
-) -> Result<()> {
-    let behavior_dir = project_dir.join(format!(
-        "behavior_pack_{}",
-        release_info.behavior_identifier
-    ));
-    let resource_dir = project_dir.join(format!(
-        "resource_pack_{}",
-        release_info.resource_identifier
-    ));
-    
-    for pack_dir in [behavior_dir, resource_dir] {
-        let manifest_path = pack_dir.join("pack_manifest.json");
-        file::update_json_file(&manifest_path, |json| {
-            json["header"]["version"] = version.clone();
-            json["modules"][0]["version"] = version.clone();
-            Ok(())
-        })?;
+fn display_relative(root: &Path, path: &Path) -> String {
+    to_slash_path(path.strip_prefix(root).unwrap_or(path))
+}
+
+/// 更新递增的包的版本, 并同步所有包清单中对这些包的依赖版本
+fn update_versions(project: &Project, versions: &[(&Pack, Version)]) -> Result<()> {
+    for (pack, version) in versions {
+        update_pack_json(project.root(), pack, &version.to_value())?;
+    }
+    for pack in project.packs() {
+        if let Some((manifest, synced)) = updated_manifest(pack, versions) {
+            for (dependency, version) in synced {
+                println!("🔗 {} 对 {} 的依赖版本: {}", pack.dir_name(), dependency.dir_name(), version);
+            }
+            file::write_json_to_file(&pack.manifest_path(), &manifest)?;
+        }
     }
-    
     Ok(())
 }
 
+fn update_pack_json(project_dir: &Path, pack: &Pack, version: &Value) -> Result<()> {
+    let path = project_dir.join(pack.kind.world_packs_file());
+    file::update_json_file(&path, |json| {
+        if let Some(entries) = json.as_array_mut() {
+            for entry in entries {
+                if entry["pack_id"].as_str() == Some(pack.uuid.as_str()) {
+                    entry["version"] = version.clone();
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+/// 返回更新版本和依赖版本后的清单以及同步了依赖版本的包, 清单无需修改时返回 `None`
+fn updated_manifest<'a>(
+    pack: &Pack,
+    versions: &[(&'a Pack, Version)],
+) -> Option<(Value, Vec<(&'a Pack, Version)>)> {
+    let mut manifest = pack.manifest().clone();
+    let mut changed = false;
+    let mut synced = Vec::new();
+    for (bumped, version) in versions {
+        if bumped.uuid == pack.uuid {
+            manifest["header"]["version"] = version.to_value();
+            manifest["modules"][0]["version"] = version.to_value();
+            changed = true;
+        } else if manifest::sync_dependency(&mut manifest, &bumped.uuid, &version.to_value()) {
+            synced.push((*bumped, *version));
+            changed = true;
+        }
+    }
+    changed.then_some((manifest, synced))
+}
+
+/// 条目按名称排序并使用固定的时间戳和权限, 相同的输入总是生成相同的压缩包
 fn package_project(
-    project_dir: &PathBuf,
-    release_info: &ReleaseInfo,
-    version: &str,
-) -> Result<String> {
-    let output_path = format!("{}/release_{}.zip", project_dir.display(), version);
-    let file = fs::File::create(&output_path)?;
-    let mut zip = zip::ZipWriter::new(file);
+    project_dir: &Path,
+    packs: &[&Pack],
+    output_path: &Path,
+    rules: &IgnoreRules,
+) -> Result<()> {
+    let mut entries = Vec::new();
+    for pack in packs {
+        entries.extend(collect_zip_entries(project_dir, pack.dir(), rules)?);
+    }
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
     
-    let behavior_dir = project_dir.join(format!(
-        "behavior_pack_{}",
-        release_info.behavior_identifier
-    ));
-    let resource_dir = project_dir.join(format!(
-        "resource_pack_{}",
-        release_info.resource_identifier
-    ));
+    let timestamp = archive_timestamp(project_dir)?;
+    let file_options = SimpleFileOptions::default()
+        .last_modified_time(timestamp)
+        .unix_permissions(0o644);
+    let dir_options = file_options.unix_permissions(0o755);
     
-    add_directory_to_zip(&mut zip, &project_dir, &behavior_dir)?;
-    add_directory_to_zip(&mut zip, &project_dir, &resource_dir)?;
+    let file = File::create(output_path)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let mut buffer = Vec::new();
+    for entry in entries {
+        match &entry.source {
+            Some(path) => {
+                zip.start_file(entry.name.as_str(), file_options)?;
+                File::open(path)?.read_to_end(&mut buffer)?;
+                zip.write_all(&buffer)?;
+                buffer.clear();
+            }
+            None => zip.add_directory(entry.name.as_str(), dir_options)?,
+        }
+    }
     
     zip.finish()?;
-    Ok(output_path)
+    Ok(())
 }
 
-fn add_directory_to_zip(
-    zip: &mut zip::ZipWriter<File>,
-    project_dir: &PathBuf,
-    src_dir: &PathBuf,
-) -> Result<()> {
+/// 压缩包条目的时间戳: 优先使用 `SOURCE_DATE_EPOCH`, 其次是项目最后一次 git 提交的时间,
+/// 都没有时使用 zip 格式的最早时间 1980-01-01 00:00:00
+fn archive_timestamp(project_dir: &Path) -> Result<zip::DateTime> {
+    let seconds = match std::env::var("SOURCE_DATE_EPOCH") {
+        Ok(value) => Some(value.trim().parse::<i64>().map_err(|_| {
+            CliError::InvalidInput(format!("无效的 SOURCE_DATE_EPOCH: '{}'", value))
+        })?),
+        Err(_) => git::last_commit_time(project_dir),
+    };
+    Ok(seconds.map(zip_datetime).unwrap_or_default())
+}
+
+/// 将 Unix 时间戳转换为 UTC 的 zip 时间, 超出 zip 可表示的范围 (1980-2107) 时使用默认值
+fn zip_datetime(seconds: i64) -> zip::DateTime {
+    let days = seconds.div_euclid(86_400);
+    let time = seconds.rem_euclid(86_400);
+
+    // 公历日期换算, 参见 http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    let (Ok(year), Ok(month), Ok(day)) = (u16::try_from(year), u8::try_from(month), u8::try_from(day)) else {
+        return zip::DateTime::default();
+    };
+    zip::DateTime::from_date_and_time(
+        year,
+        month,
+        day,
+        (time / 3_600) as u8,
+        (time % 3_600 / 60) as u8,
+        (time % 60) as u8,
+    )
+    .unwrap_or_default()
+}
+
+/// 压缩包中的一个条目, `source` 为 `None` 时表示目录
+struct ZipEntry {
+    name: String,
+    source: Option<PathBuf>,
+}
+
+/// 按名称排序列出包目录中需要打包的条目, 跳过被忽略的路径以及不包含文件的目录
+fn collect_zip_entries(
+    project_dir: &Path,
+    src_dir: &Path,
+    rules: &IgnoreRules,
+) -> Result<Vec<ZipEntry>> {
     if !src_dir.is_dir() {
-        return Err(crate::error::CliError::InvalidData(
+        return Err(CliError::InvalidData(
             format!("{} 不是目录", src_dir.display())
         ));
     }
     
-    if count_files(src_dir)? == 0 {
-        return Ok(());
-    }
-    
-    let options = SimpleFileOptions::default();
-    let mut buffer = Vec::new();
-    
-    for entry in walkdir::WalkDir::new(src_dir) {
+    let mut entries = Vec::new();
+    let mut walker = walkdir::WalkDir::new(src_dir).into_iter();
+    while let Some(entry) = walker.next() {
         let entry = entry?;
         let path = entry.path();
         let relative_path = path.strip_prefix(project_dir)
-            .map_err(|e| crate::error::CliError::InvalidData(e.to_string()))?;
-        
-        let path_str = relative_path
-            .to_str()
-            .ok_or_else(|| crate::error::CliError::InvalidData(
+            .map_err(|e| CliError::InvalidData(e.to_string()))?;
+        if relative_path.to_str().is_none() {
+            return Err(CliError::InvalidData(
                 format!("{:?} 不是有效的 UTF-8 路径", relative_path)
-            ))?;
+            ));
+        }
+        let name = to_slash_path(relative_path);
+        let is_dir = entry.file_type().is_dir();
         
-        if path.is_file() {
-            if path_str.ends_with(".gitkeep") {
-                continue;
+        if rules.is_ignored(&name, is_dir) {
+            if is_dir {
+                walker.skip_current_dir();
             }
-            zip.start_file(path_str, options)?;
-            let mut f = File::open(path)?;
-            f.read_to_end(&mut buffer)?;
-            zip.write_all(&buffer)?;
-            buffer.clear();
-        } else if !relative_path.as_os_str().is_empty() && count_files(path)? > 0 {
-            zip.add_directory(path_str, options)?;
+            continue;
+        }
+        if is_dir {
+            entries.push(ZipEntry { name, source: None });
+        } else if path.is_file() {
+            entries.push(ZipEntry {
+                name,
+                source: Some(path.to_path_buf()),
+            });
         }
     }
     
-    Ok(())
+    let used_dirs: HashSet<String> = entries
+        .iter()
+        .filter(|entry| entry.source.is_some())
+        .flat_map(|entry| entry.name.match_indices('/').map(|(i, _)| entry.name[..i].to_string()))
+        .collect();
+    entries.retain(|entry| entry.source.is_some() || used_dirs.contains(&entry.name));
+    // walkdir 按文件系统的目录顺序遍历, 排序后指纹和打包结果才与文件系统无关
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    
+    Ok(entries)
 }
 
-fn count_files(dir: &Path) -> Result<usize> {
-    let mut count = 0;
-    for entry in walkdir::WalkDir::new(dir) {
-        let entry = entry?;
-        if entry.path().is_file() 
-            && !entry.path().display().to_string().ends_with(".gitkeep") {
-            count += 1;
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct ReleaseState {
+    packs: BTreeMap<String, PackState>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct PackState {
+    version: Version,
+    fingerprint: String,
+}
+
+impl ReleaseState {
+    fn load(project_dir: &Path) -> Result<Self> {
+        let path = project_dir.join(STATE_FILE);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_value(file::read_file_to_json(&path)?)?)
+    }
+
+    /// 没有发布记录的包视为有变化
+    fn is_changed(&self, project_dir: &Path, pack: &Pack, rules: &IgnoreRules) -> Result<bool> {
+        match self.packs.get(&pack.uuid) {
+            Some(state) => Ok(state.fingerprint != fingerprint(project_dir, pack, rules)?),
+            None => Ok(true),
+        }
+    }
+
+    /// 发布后重新读取各个包的版本和内容, 保存到状态文件
+    fn record(project_dir: &Path, packs: &[&Pack], rules: &IgnoreRules) -> Result<()> {
+        let mut state = Self::load(project_dir)?;
+        let project = Project::load(project_dir)?;
+        for pack in packs {
+            let pack = project.find_pack(&pack.uuid)?;
+            state.packs.insert(
+                pack.uuid.clone(),
+                PackState {
+                    version: pack.version,
+                    fingerprint: fingerprint(project_dir, pack, rules)?,
+                },
+            );
         }
+        file::write_json_to_file(&project_dir.join(STATE_FILE), &serde_json::to_value(&state)?)
+    }
+}
+
+/// 包中所有会被打包的文件的路径和内容的 SHA-1
+fn fingerprint(project_dir: &Path, pack: &Pack, rules: &IgnoreRules) -> Result<String> {
+    let mut hasher = sha1_smol::Sha1::new();
+    for entry in collect_zip_entries(project_dir, pack.dir(), rules)? {
+        if let Some(path) = entry.source {
+            hasher.update(entry.name.as_bytes());
+            hasher.update(&[0]);
+            hasher.update(&fs::read(&path)?);
+            hasher.update(&[0]);
+        }
+    }
+    Ok(hasher.digest().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(datetime: zip::DateTime) -> (u16, u8, u8, u8, u8, u8) {
+        (
+            datetime.year(),
+            datetime.month(),
+            datetime.day(),
+            datetime.hour(),
+            datetime.minute(),
+            datetime.second(),
+        )
+    }
+
+    #[test]
+    fn converts_unix_seconds_to_civil_time() {
+        // 2024-02-29T12:34:56Z, 闰日
+        assert_eq!(parts(zip_datetime(1_709_210_096)), (2024, 2, 29, 12, 34, 56));
+        // 2000-03-01T00:00:00Z, 能被 400 整除的闰年之后
+        assert_eq!(parts(zip_datetime(951_868_800)), (2000, 3, 1, 0, 0, 0));
+        // 2100-03-01T00:00:00Z, 2100 年不是闰年
+        assert_eq!(parts(zip_datetime(4_107_542_400)), (2100, 3, 1, 0, 0, 0));
+        // 1980-01-01T00:00:00Z, zip 格式能表示的最早时间
+        assert_eq!(parts(zip_datetime(315_532_800)), (1980, 1, 1, 0, 0, 0));
+        // 2107-12-31T23:59:58Z, zip 格式能表示的最晚时间
+        assert_eq!(parts(zip_datetime(4_354_819_198)), (2107, 12, 31, 23, 59, 58));
+    }
+
+    #[test]
+    fn falls_back_to_default_outside_zip_range() {
+        let default = parts(zip::DateTime::default());
+        assert_eq!(parts(zip_datetime(0)), default);
+        assert_eq!(parts(zip_datetime(315_532_799)), default);
+        assert_eq!(parts(zip_datetime(4_354_819_200)), default);
+        assert_eq!(parts(zip_datetime(-86_400)), default);
     }
-    Ok(count)
 }
@@ -0,0 +1,247 @@
+use crate::commands::{
+    TemplateArgs, TemplateCacheArgs, TemplateCacheCommands, TemplateCommands, TemplateInfoArgs,
+    TemplateListArgs, TemplateValidateArgs,
+};
+use crate::config::Config;
+use crate::error::{CliError, Result};
+use crate::template::TemplateConfig;
+use crate::template::cache::{self, TemplateCache};
+use crate::template::hook::HookAction;
+use crate::template::lint::{self, Severity};
+use crate::template::source::TemplateSource;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub fn execute(args: &TemplateArgs, temp_dir: &Path) {
+    let result = match &args.command {
+        TemplateCommands::List(args) => run_list(args),
+        TemplateCommands::Info(args) => run_info(args, temp_dir),
+        TemplateCommands::Validate(args) => run_validate(args),
+        TemplateCommands::Cache(args) => run_cache(args),
+    };
+    if let Err(e) = result {
+        eprintln!("错误: {}", e);
+        // 检查未通过时以非零状态退出, 以便在 CI 中使用
+        if matches!(args.command, TemplateCommands::Validate(_)) {
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_list(args: &TemplateListArgs) -> Result<()> {
+    let config = Config::load();
+    let cache = TemplateCache::new(config.template_cache_dir());
+
+    let mut sources: Vec<(String, PathBuf)> = Vec::new();
+    match cache.checkout_or_cached(&config.repo_url, args.template_ref.as_deref()) {
+        Ok(entry) => sources.push((
+            format!("{} ({})", config.repo_url, cache::short_commit(&entry.commit)),
+            entry.repo_dir().join("examples"),
+        )),
+        Err(e) => eprintln!("警告: 无法获取 {}: {}", config.repo_url, e),
+    }
+    for dir in &config.template_dirs {
+        sources.push((dir.clone(), PathBuf::from(dir)));
+    }
+
+    for (label, dir) in sources {
+        println!("{}:", label);
+        let templates = discover_templates(&dir)?;
+        if templates.is_empty() {
+            println!("  (无可用模板)");
+        }
+        for (target, template_dir) in templates {
+            match TemplateConfig::load(&template_dir) {
+                Ok(template) => println!(
+                    "  {:<16} {}",
+                    target,
+                    describe_template(&target, &template)
+                ),
+                Err(e) => println!("  {:<16} 无效的 template.toml: {}", target, e),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn run_info(args: &TemplateInfoArgs, temp_dir: &Path) -> Result<()> {
+    let config = Config::load();
+    let cache = TemplateCache::new(config.template_cache_dir());
+    let source = TemplateSource::parse(
+        args.target.as_deref(),
+        args.template_ref.as_deref(),
+        &config,
+    );
+    let template_dir = source.fetch(temp_dir, &cache)?;
+    let template = TemplateConfig::load(&template_dir)?;
+
+    println!("名称: {}", template.template.name);
+    println!("描述: {}", template.template.description);
+    println!("来源: {}", source);
+
+    println!("变量:");
+    let mut variables: Vec<_> = template.variables.iter().collect();
+    variables.sort_by(|a, b| a.0.cmp(b.0));
+    for (key, var) in variables {
+        let mut details = vec![var.var_type.to_string()];
+        if var.required {
+            details.push("必需".to_string());
+        }
+        if let Some(default) = var.default_text() {
+            details.push(format!("默认: {}", default));
+        }
+        if !var.choices.is_empty() {
+            details.push(format!("可选: {}", var.choices.join("/")));
+        }
+        if let Some(pattern) = &var.pattern {
+            details.push(format!("格式: {}", pattern));
+        }
+        println!("  {:<28} {} [{}]", key, var.description, details.join(", "));
+    }
+
+    if !template.renames.is_empty() {
+        println!("重命名:");
+        for rule in &template.renames {
+            println!("  {} -> {}", rule.from, rule.to);
+        }
+    }
+
+    println!("处理的扩展名: {}", template.process.file_extensions.join(", "));
+    for style in &template.process.placeholders {
+        println!(
+            "  {} 额外占位符: {}var{}",
+            style.extensions.join(", "),
+            style.prefix,
+            style.suffix
+        );
+    }
+
+    for (label, rules) in [("包含", &template.include), ("排除", &template.exclude)] {
+        for rule in rules {
+            let when = rule.when.as_deref().map(|w| format!(" (当 {})", w)).unwrap_or_default();
+            println!("{}: {}{}", label, rule.paths.join(", "), when);
+        }
+    }
+
+    if !template.hooks.is_empty() {
+        println!("生成后操作:");
+        for hook in &template.hooks {
+            let when = hook.when.as_deref().map(|w| format!(" (当 {})", w)).unwrap_or_default();
+            let marker = if matches!(hook.action, HookAction::Shell { .. }) { " [需确认]" } else { "" };
+            println!("  {}{}{}", hook.action.describe(), when, marker);
+        }
+    }
+
+    Ok(())
+}
+
+fn run_validate(args: &TemplateValidateArgs) -> Result<()> {
+    let dir = PathBuf::from(&args.dir);
+    if !dir.join("template.toml").is_file() {
+        return Err(CliError::NotFound(format!(
+            "{} 中没有 template.toml",
+            dir.display()
+        )));
+    }
+
+    let issues = lint::validate(&dir)?;
+    for issue in &issues {
+        println!("{}", issue);
+    }
+
+    let errors = issues.iter().filter(|i| i.severity == Severity::Error).count();
+    let warnings = issues.len() - errors;
+    if errors > 0 {
+        return Err(CliError::InvalidData(format!(
+            "模板检查未通过: {} 个错误, {} 个警告",
+            errors, warnings
+        )));
+    }
+    println!("成功: 模板检查通过 ({} 个警告)", warnings);
+    Ok(())
+}
+
+/// 目录本身是模板时返回自身, 否则返回其下包含 `template.toml` 的子目录
+fn discover_templates(dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    if dir.join("template.toml").is_file() {
+        let name = dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| dir.display().to_string());
+        return Ok(vec![(name, dir.to_path_buf())]);
+    }
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut templates = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.join("template.toml").is_file() {
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            templates.push((name, path));
+        }
+    }
+    templates.sort();
+    Ok(templates)
+}
+
+fn describe_template(target: &str, template: &TemplateConfig) -> String {
+    if template.template.name == target {
+        template.template.description.clone()
+    } else {
+        format!("{} - {}", template.template.name, template.template.description)
+    }
+}
+
+fn run_cache(args: &TemplateCacheArgs) -> Result<()> {
+    let config = Config::load();
+    let cache = TemplateCache::new(config.template_cache_dir());
+
+    match &args.command {
+        TemplateCacheCommands::List => {
+            let entries = cache.list()?;
+            println!("缓存目录: {}", cache.root().display());
+            if entries.is_empty() {
+                println!("暂无缓存模板");
+                return Ok(());
+            }
+            for entry in entries {
+                println!(
+                    " - {} @ {} ({}, {})",
+                    entry.repo_url,
+                    entry.git_ref.as_deref().unwrap_or("HEAD"),
+                    cache::short_commit(&entry.commit),
+                    format_age(entry.updated_at)
+                );
+            }
+        }
+        TemplateCacheCommands::Prune(prune_args) => {
+            let removed = cache.prune(prune_args.older_than)?;
+            for entry in &removed {
+                println!(
+                    " - 已删除: {} @ {}",
+                    entry.repo_url,
+                    entry.git_ref.as_deref().unwrap_or("HEAD")
+                );
+            }
+            println!("成功: 已清理 {} 个缓存模板", removed.len());
+        }
+    }
+
+    Ok(())
+}
+
+fn format_age(updated_at: u64) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let days = now.saturating_sub(updated_at) / (24 * 60 * 60);
+    match days {
+        0 => "今天更新".to_string(),
+        n => format!("{} 天前更新", n),
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::env;
 use std::fs;
 use std::path::PathBuf;
 
@@ -6,6 +7,12 @@ use std::path::PathBuf;
 pub struct Config {
     #[serde(default = "default_repo_url")]
     pub repo_url: String,
+    /// 模板缓存目录, 默认位于系统缓存目录下
+    #[serde(default)]
+    pub cache_dir: Option<String>,
+    /// 额外的本地模板目录, 可以是单个模板或包含多个模板的目录
+    #[serde(default)]
+    pub template_dirs: Vec<String>,
 }
 
 fn default_repo_url() -> String {
@@ -16,6 +23,8 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             repo_url: default_repo_url(),
+            cache_dir: None,
+            template_dirs: Vec::new(),
         }
     }
 }
@@ -24,15 +33,25 @@ impl Config {
     pub fn load() -> Self {
         let config_path = Self::config_path();
         
-        if let Ok(content) = fs::read_to_string(&config_path) {
-            if let Ok(config) = serde_json::from_str(&content) {
-                return config;
-            }
+        if let Ok(content) = fs::read_to_string(&config_path)
+            && let Ok(config) = serde_json::from_str(&content)
+        {
+            return config;
         }
         
         Self::default()
     }
 
+    pub fn template_cache_dir(&self) -> PathBuf {
+        if let Some(dir) = &self.cache_dir {
+            return PathBuf::from(dir);
+        }
+        dirs::cache_dir()
+            .unwrap_or_else(env::temp_dir)
+            .join("emod-cli")
+            .join("templates")
+    }
+
     fn config_path() -> PathBuf {
         let home = dirs::home_dir().expect("无法获取用户主目录");
         home.join(".emod-cli.json")
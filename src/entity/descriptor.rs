@@ -0,0 +1,94 @@
+use crate::error::Result;
+use crate::utils::file;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+pub const DESCRIPTOR_FILE: &str = "emod.toml";
+
+/// 项目描述文件 `emod.toml`, 由 `create` 写入, 记录项目创建时确定的信息
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ProjectDescriptor {
+    pub project: ProjectSection,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<TemplateSection>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub behavior_packs: Vec<PackEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resource_packs: Vec<PackEntry>,
+    #[serde(default, skip_serializing_if = "PackageSection::is_empty")]
+    pub package: PackageSection,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ProjectSection {
+    pub name: String,
+    pub namespace: String,
+    #[serde(default)]
+    pub python_module: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct TemplateSection {
+    pub source: String,
+    #[serde(rename = "ref", default, skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+}
+
+/// `release` 打包设置
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct PackageSection {
+    /// 额外的 gitignore 风格忽略规则, 在内置规则之后、`.emodignore` 之前生效
+    #[serde(default)]
+    pub ignore: Vec<String>,
+}
+
+impl PackageSection {
+    fn is_empty(&self) -> bool {
+        self.ignore.is_empty()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct PackEntry {
+    pub uuid: String,
+    /// 相对项目根目录的包目录
+    pub dir: String,
+}
+
+impl ProjectDescriptor {
+    /// 读取项目中的 `emod.toml`, 文件不存在时返回 `None`
+    pub fn load(project_dir: &Path) -> Result<Option<Self>> {
+        let path = project_dir.join(DESCRIPTOR_FILE);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        Ok(Some(toml::from_str(&content)?))
+    }
+
+    pub fn save(&self, project_dir: &Path) -> Result<()> {
+        let content = toml::to_string_pretty(self).map_err(|e| {
+            crate::error::CliError::InvalidData(format!("无法序列化 {}: {}", DESCRIPTOR_FILE, e))
+        })?;
+        fs::write(project_dir.join(DESCRIPTOR_FILE), content)?;
+        Ok(())
+    }
+}
+
+/// 在项目根目录的子目录中查找 `pack_manifest.json` 的 `header.uuid` 为 `uuid` 的包目录
+pub fn find_pack_dir(project_dir: &Path, uuid: &str) -> Result<Option<String>> {
+    let mut dirs: Vec<_> = fs::read_dir(project_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("pack_manifest.json").is_file())
+        .collect();
+    dirs.sort_by_key(|entry| entry.file_name());
+
+    for entry in dirs {
+        let manifest = file::read_file_to_json(&entry.path().join("pack_manifest.json"))?;
+        if manifest["header"]["uuid"].as_str() == Some(uuid) {
+            return Ok(Some(entry.file_name().to_string_lossy().to_string()));
+        }
+    }
+    Ok(None)
+}
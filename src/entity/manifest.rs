@@ -0,0 +1,48 @@
+use crate::entity::project::Pack;
+use crate::error::Result;
+use crate::utils::file;
+use serde_json::{Value, json};
+
+/// 在清单中添加或更新对 `uuid` 的依赖, 返回清单是否被修改
+pub fn add_dependency(manifest: &mut Value, uuid: &str, version: &Value) -> bool {
+    if !manifest["dependencies"].is_array() {
+        manifest["dependencies"] = json!([]);
+    }
+    if sync_dependency(manifest, uuid, version) {
+        return true;
+    }
+    let dependencies = manifest["dependencies"].as_array_mut().unwrap();
+    if dependencies.iter().any(|dep| dep["uuid"].as_str() == Some(uuid)) {
+        return false;
+    }
+    dependencies.push(json!({ "uuid": uuid, "version": version }));
+    true
+}
+
+/// 将清单中对 `uuid` 的依赖版本更新为 `version`, 返回清单是否被修改
+pub fn sync_dependency(manifest: &mut Value, uuid: &str, version: &Value) -> bool {
+    let Some(dependencies) = manifest["dependencies"].as_array_mut() else {
+        return false;
+    };
+    let mut changed = false;
+    for dep in dependencies {
+        if dep["uuid"].as_str() == Some(uuid) && &dep["version"] != version {
+            dep["version"] = version.clone();
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// 让两个包在 `pack_manifest.json` 中互相声明依赖, 版本取对方清单头部的版本
+pub fn link_packs(a: &Pack, b: &Pack) -> Result<bool> {
+    let mut changed = false;
+    for (pack, other) in [(a, b), (b, a)] {
+        let mut manifest = file::read_file_to_json(&pack.manifest_path())?;
+        if add_dependency(&mut manifest, &other.uuid, &other.manifest()["header"]["version"]) {
+            file::write_json_to_file(&pack.manifest_path(), &manifest)?;
+            changed = true;
+        }
+    }
+    Ok(changed)
+}
@@ -0,0 +1,231 @@
+use crate::error::{CliError, Result};
+use crate::template::filter;
+
+const MAX_NAME_LEN: usize = 64;
+const INVALID_CHARS: &[char] = &['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+const RESERVED_NAMESPACES: &[&str] = &["minecraft", "netease"];
+const RESERVED_FILE_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "LPT1", "LPT2", "LPT3",
+];
+
+/// 项目名称及其在不同场景下使用的安全形式
+#[derive(Debug, Clone)]
+pub struct ProjectName {
+    /// 显示名称, 即用户输入的名称
+    pub display: String,
+    /// Python 包名前缀, 仅包含 ASCII 字母、数字和下划线
+    pub python_module: String,
+    /// 物品、方块等标识符使用的命名空间
+    pub namespace: String,
+    /// 项目目录名
+    pub pack_folder: String,
+}
+
+impl ProjectName {
+    pub fn parse(name: &str, namespace: Option<&str>) -> Result<Self> {
+        let display = name.trim().to_string();
+        validate_display_name(&display)?;
+
+        let pack_folder = display.trim_end_matches(['.', ' ']).to_string();
+        if pack_folder.is_empty()
+            || RESERVED_FILE_NAMES
+                .iter()
+                .any(|reserved| reserved.eq_ignore_ascii_case(&pack_folder))
+        {
+            return Err(CliError::InvalidInput(format!(
+                "项目名称 '{}' 不能用作目录名",
+                display
+            )));
+        }
+
+        let namespace = match namespace {
+            Some(namespace) => {
+                validate_namespace(namespace)?;
+                namespace.to_string()
+            }
+            None => derive_namespace(&display),
+        };
+
+        let python_module = derive_python_module(&display, &namespace);
+
+        Ok(Self {
+            display,
+            python_module,
+            namespace,
+            pack_folder,
+        })
+    }
+}
+
+fn validate_display_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        return Err(CliError::InvalidInput("项目名称不能为空".to_string()));
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(CliError::InvalidInput(format!(
+            "项目名称不能超过 {} 个字符",
+            MAX_NAME_LEN
+        )));
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| c.is_control() || INVALID_CHARS.contains(c))
+    {
+        return Err(CliError::InvalidInput(format!(
+            "项目名称 '{}' 包含无效字符 {:?}",
+            name, c
+        )));
+    }
+    Ok(())
+}
+
+/// 命名空间只能包含小写字母、数字和下划线, 且不能以数字开头
+pub fn validate_namespace(namespace: &str) -> Result<()> {
+    let mut chars = namespace.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        return Err(CliError::InvalidInput(format!(
+            "无效的命名空间 '{}': 只能包含小写字母、数字和下划线, 且不能以数字开头",
+            namespace
+        )));
+    }
+    if RESERVED_NAMESPACES.contains(&namespace) {
+        return Err(CliError::InvalidInput(format!(
+            "命名空间 '{}' 为保留名称",
+            namespace
+        )));
+    }
+    Ok(())
+}
+
+fn derive_namespace(name: &str) -> String {
+    let snake = filter::apply_filter(&ascii_words(name), "snake").unwrap_or_default();
+    let namespace = match snake.chars().next() {
+        None => fallback_identifier(name),
+        Some(c) if c.is_ascii_digit() => format!("mod_{}", snake),
+        Some(_) => snake,
+    };
+    if RESERVED_NAMESPACES.contains(&namespace.as_str()) {
+        format!("{}_mod", namespace)
+    } else {
+        namespace
+    }
+}
+
+/// ASCII 标识符名称保持原样仅首字母小写, 与旧版本生成的包名一致
+fn derive_python_module(name: &str, namespace: &str) -> String {
+    if is_ascii_identifier(name) {
+        return filter::apply_filter(name, "lower_first").unwrap_or_default();
+    }
+    let mut module = filter::apply_filter(&ascii_words(name), "camel").unwrap_or_default();
+    if module.is_empty() {
+        module = filter::apply_filter(namespace, "camel").unwrap_or_default();
+    }
+    match module.chars().next() {
+        None => fallback_identifier(name),
+        Some(c) if c.is_ascii_digit() => format!("mod{}", module),
+        Some(_) => module,
+    }
+}
+
+fn is_ascii_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// 非 ASCII 字符替换为空格, 只保留可用于标识符的单词
+fn ascii_words(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { ' ' })
+        .collect()
+}
+
+/// 名称中没有可用的 ASCII 字符时, 使用名称的稳定哈希生成标识符
+fn fallback_identifier(name: &str) -> String {
+    let hash = name.bytes().fold(0x811c9dc5u32, |hash, b| {
+        (hash ^ b as u32).wrapping_mul(0x01000193)
+    });
+    format!("mod_{:08x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(name: &str) -> ProjectName {
+        ProjectName::parse(name, None).unwrap()
+    }
+
+    #[test]
+    fn rejects_empty_and_invalid_names() {
+        for name in ["", "   ", "a/b", "CON", "...", &"x".repeat(MAX_NAME_LEN + 1)] {
+            assert!(
+                matches!(ProjectName::parse(name, None), Err(CliError::InvalidInput(_))),
+                "'{}' should be rejected",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn keeps_ascii_identifiers() {
+        let name = parse("MyHTTPMod");
+        assert_eq!(name.display, "MyHTTPMod");
+        assert_eq!(name.pack_folder, "MyHTTPMod");
+        assert_eq!(name.python_module, "myHTTPMod");
+        assert_eq!(name.namespace, "my_http_mod");
+    }
+
+    #[test]
+    fn prefixes_names_starting_with_a_digit() {
+        let name = parse("2048 Game");
+        assert_eq!(name.namespace, "mod_2048_game");
+        assert_eq!(name.python_module, "mod2048Game");
+    }
+
+    #[test]
+    fn derives_stable_identifiers_for_non_ascii_names() {
+        let name = parse("我的模组");
+        assert_eq!(name.pack_folder, "我的模组");
+        assert_eq!(name.namespace, fallback_identifier("我的模组"));
+        assert!(name.namespace.starts_with("mod_") && name.namespace.len() == 12);
+        assert_eq!(parse("我的模组").namespace, name.namespace);
+        assert_ne!(parse("你的模组").namespace, name.namespace);
+        assert!(is_ascii_identifier(&name.python_module));
+    }
+
+    #[test]
+    fn keeps_ascii_words_of_mixed_names() {
+        let name = parse("我的 Cool Mod");
+        assert_eq!(name.namespace, "cool_mod");
+        assert_eq!(name.python_module, "coolMod");
+    }
+
+    #[test]
+    fn avoids_reserved_namespaces() {
+        assert_eq!(derive_namespace("Minecraft"), "minecraft_mod");
+        assert_eq!(derive_namespace("netease"), "netease_mod");
+    }
+
+    #[test]
+    fn validates_explicit_namespaces() {
+        let name = ProjectName::parse("My Mod", Some("steve")).unwrap();
+        assert_eq!(name.namespace, "steve");
+        assert_eq!(name.python_module, "myMod");
+
+        for namespace in ["", "Steve", "1steve", "ste-ve", "minecraft", "netease"] {
+            let result = ProjectName::parse("My Mod", Some(namespace));
+            assert!(
+                matches!(result, Err(CliError::InvalidInput(_))),
+                "namespace '{}' should be rejected",
+                namespace
+            );
+        }
+    }
+}
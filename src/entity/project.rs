@@ -1,15 +1,281 @@
+use crate::entity::descriptor::{DESCRIPTOR_FILE, PackEntry, ProjectDescriptor};
+use crate::entity::name::ProjectName;
+use crate::entity::version::Version;
+use crate::error::{CliError, Result};
+use crate::utils::file;
+use serde_json::Value;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+pub const MANIFEST_FILE: &str = "pack_manifest.json";
+
 pub struct ProjectInfo {
-    pub name: String,
-    pub lower_name: String,
+    pub name: ProjectName,
     pub behavior_pack_uuid: String,
     pub resource_pack_uuid: String,
     pub behavior_module_uuid: String,
     pub resource_module_uuid: String,
 }
 
-pub struct ReleaseInfo {
-    pub behavior_version: Vec<u32>,
-    pub resource_version: Vec<u32>,
-    pub behavior_identifier: String,
-    pub resource_identifier: String,
-}
\ No newline at end of file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackKind {
+    Behavior,
+    Resource,
+}
+
+impl PackKind {
+    /// 项目根目录中登记该类型包的列表文件
+    pub fn world_packs_file(self) -> &'static str {
+        match self {
+            PackKind::Behavior => "world_behavior_packs.json",
+            PackKind::Resource => "world_resource_packs.json",
+        }
+    }
+
+    /// 未记录在 `emod.toml` 中时包目录的默认前缀
+    fn dir_prefix(self) -> &'static str {
+        match self {
+            PackKind::Behavior => "behavior_pack",
+            PackKind::Resource => "resource_pack",
+        }
+    }
+}
+
+impl fmt::Display for PackKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackKind::Behavior => write!(f, "行为包"),
+            PackKind::Resource => write!(f, "资源包"),
+        }
+    }
+}
+
+/// 登记在 `world_*_packs.json` 中的一个包及其 `pack_manifest.json`
+#[derive(Debug, Clone)]
+pub struct Pack {
+    pub kind: PackKind,
+    pub uuid: String,
+    /// `world_*_packs.json` 中登记的版本
+    pub version: Version,
+    dir: PathBuf,
+    manifest: Value,
+}
+
+impl Pack {
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn manifest_path(&self) -> PathBuf {
+        self.dir.join(MANIFEST_FILE)
+    }
+
+    pub fn manifest(&self) -> &Value {
+        &self.manifest
+    }
+
+    pub fn min_engine_version(&self) -> Option<Version> {
+        Version::from_value(&self.manifest["header"]["min_engine_version"]).ok()
+    }
+
+    /// 包目录名, 用于 `--pack` 选择和输出
+    pub fn dir_name(&self) -> String {
+        self.dir
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    /// `selector` 为包目录名、完整 UUID 或 UUID 前缀
+    fn matches(&self, selector: &str) -> bool {
+        self.dir_name() == selector || self.uuid.starts_with(&selector.to_ascii_lowercase())
+    }
+}
+
+/// 项目的统一视图: 包列表、各个包的清单、脚本目录以及 `emod.toml` 中的设置
+#[derive(Debug, Clone)]
+pub struct Project {
+    root: PathBuf,
+    descriptor: Option<ProjectDescriptor>,
+    behavior_packs: Vec<Pack>,
+    resource_packs: Vec<Pack>,
+    scripts_root: Option<PathBuf>,
+}
+
+impl Project {
+    pub fn load(root: &Path) -> Result<Self> {
+        let descriptor = ProjectDescriptor::load(root)?;
+        let recorded = |kind: PackKind| -> &[PackEntry] {
+            match (&descriptor, kind) {
+                (Some(d), PackKind::Behavior) => &d.behavior_packs,
+                (Some(d), PackKind::Resource) => &d.resource_packs,
+                (None, _) => &[],
+            }
+        };
+
+        let behavior_packs = load_packs(root, PackKind::Behavior, recorded(PackKind::Behavior))?;
+        let resource_packs = load_packs(root, PackKind::Resource, recorded(PackKind::Resource))?;
+        let scripts_root = behavior_packs
+            .first()
+            .and_then(|pack| find_scripts_root(pack.dir(), descriptor.as_ref()));
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            descriptor,
+            behavior_packs,
+            resource_packs,
+            scripts_root,
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// `emod.toml` 中记录的项目名称, 未记录时使用项目目录名
+    pub fn name(&self) -> String {
+        match &self.descriptor {
+            Some(descriptor) => descriptor.project.name.clone(),
+            None => file::absolute_path(&self.root)
+                .ok()
+                .and_then(|path| path.file_name().map(|s| s.to_string_lossy().to_string()))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// 主行为包, 即列表中的第一个行为包
+    pub fn behavior_pack(&self) -> Result<&Pack> {
+        self.behavior_packs.first().ok_or_else(|| {
+            CliError::NotFound(format!("{} 中没有登记行为包", PackKind::Behavior.world_packs_file()))
+        })
+    }
+
+    /// 主资源包, 即列表中的第一个资源包
+    pub fn resource_pack(&self) -> Result<&Pack> {
+        self.resource_packs.first().ok_or_else(|| {
+            CliError::NotFound(format!("{} 中没有登记资源包", PackKind::Resource.world_packs_file()))
+        })
+    }
+
+    /// 全部包, 行为包在前
+    pub fn packs(&self) -> impl Iterator<Item = &Pack> {
+        self.behavior_packs.iter().chain(self.resource_packs.iter())
+    }
+
+    /// `emod.toml` 中 `[package] ignore` 的规则
+    pub fn package_ignore(&self) -> &[String] {
+        self.descriptor
+            .as_ref()
+            .map_or(&[], |descriptor| descriptor.package.ignore.as_slice())
+    }
+
+    /// 按列表顺序将第 N 个行为包与第 N 个资源包配对
+    pub fn pairs(&self) -> Vec<(&Pack, &Pack)> {
+        self.behavior_packs.iter().zip(self.resource_packs.iter()).collect()
+    }
+
+    /// 按目录名或 UUID 查找包, UUID 前缀匹配到多个包时报错
+    pub fn find_pack(&self, selector: &str) -> Result<&Pack> {
+        let matched: Vec<&Pack> = self.packs().filter(|pack| pack.matches(selector)).collect();
+        match matched.as_slice() {
+            [pack] => Ok(pack),
+            [] => Err(CliError::NotFound(format!(
+                "包 '{}', 可选: {}",
+                selector,
+                self.packs().map(|pack| pack.dir_name()).collect::<Vec<_>>().join(", ")
+            ))),
+            _ => Err(CliError::InvalidInput(format!(
+                "'{}' 匹配到多个包: {}",
+                selector,
+                matched.iter().map(|pack| pack.dir_name()).collect::<Vec<_>>().join(", ")
+            ))),
+        }
+    }
+
+    /// 按 `--pack` 选择包, 未指定时返回全部包
+    pub fn select_packs(&self, selectors: &[String]) -> Result<Vec<&Pack>> {
+        if selectors.is_empty() {
+            return Ok(self.packs().collect());
+        }
+        let mut packs: Vec<&Pack> = Vec::new();
+        for selector in selectors {
+            let pack = self.find_pack(selector)?;
+            if !packs.iter().any(|p| p.uuid == pack.uuid) {
+                packs.push(pack);
+            }
+        }
+        Ok(packs)
+    }
+
+    /// 行为包中包含 `modMain.py` 的脚本目录
+    pub fn scripts_root(&self) -> Option<&Path> {
+        self.scripts_root.as_deref()
+    }
+}
+
+/// 读取 `world_*_packs.json` 中登记的全部包, `emod.toml` 中记录的包排在前面
+fn load_packs(root: &Path, kind: PackKind, recorded: &[PackEntry]) -> Result<Vec<Pack>> {
+    let list_path = root.join(kind.world_packs_file());
+    let json = file::read_file_to_json(&list_path)?;
+    let entries = json.as_array().ok_or_else(|| {
+        CliError::InvalidData(format!("{} 不是数组", list_path.display()))
+    })?;
+
+    let mut packs = Vec::new();
+    for entry in entries {
+        let uuid = entry["pack_id"]
+            .as_str()
+            .ok_or_else(|| CliError::InvalidData(format!("{} 中存在无效的 pack_id", list_path.display())))?
+            .to_string();
+        let version = Version::from_value(&entry["version"])?;
+
+        let dir_name = recorded
+            .iter()
+            .find(|pack| pack.uuid == uuid)
+            .map(|pack| pack.dir.clone())
+            .unwrap_or_else(|| format!("{}_{}", kind.dir_prefix(), short_identifier(&uuid)));
+        let dir = root.join(&dir_name);
+        if !dir.is_dir() {
+            return Err(CliError::NotFound(format!(
+                "{} {} 的目录 {} (可在 {} 中指定)",
+                kind,
+                uuid,
+                dir.display(),
+                DESCRIPTOR_FILE
+            )));
+        }
+        let manifest = file::read_file_to_json(&dir.join(MANIFEST_FILE))?;
+
+        packs.push(Pack {
+            kind,
+            uuid,
+            version,
+            dir,
+            manifest,
+        });
+    }
+
+    let is_recorded = |pack: &Pack| recorded.iter().any(|entry| entry.uuid == pack.uuid);
+    packs.sort_by_key(|pack| !is_recorded(pack));
+    Ok(packs)
+}
+
+fn find_scripts_root(pack_dir: &Path, descriptor: Option<&ProjectDescriptor>) -> Option<PathBuf> {
+    if let Some(descriptor) = descriptor {
+        let dir = pack_dir.join(format!("{}Scripts", descriptor.project.python_module));
+        if dir.join("modMain.py").is_file() {
+            return Some(dir);
+        }
+    }
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(pack_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.join("modMain.py").is_file())
+        .collect();
+    dirs.sort();
+    dirs.into_iter().next()
+}
+
+fn short_identifier(uuid: &str) -> String {
+    uuid.chars().take(8).collect()
+}
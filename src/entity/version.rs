@@ -0,0 +1,145 @@
+use crate::error::{CliError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+/// 包版本 `major.minor.patch`, 在 JSON 中以 `[major, minor, patch]` 数组表示
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(from = "[u32; 3]", into = "[u32; 3]")]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Version {
+    pub fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// 从清单或包列表中的版本数组读取
+    pub fn from_value(value: &Value) -> Result<Self> {
+        let parts = value
+            .as_array()
+            .ok_or_else(|| CliError::InvalidData(format!("版本格式无效: {}", value)))?;
+        let numbers: Vec<u32> = parts
+            .iter()
+            .map(|v| v.as_u64().and_then(|n| u32::try_from(n).ok()))
+            .collect::<Option<_>>()
+            .ok_or_else(|| CliError::InvalidData(format!("版本号格式无效: {}", value)))?;
+        match numbers.as_slice() {
+            [major, minor, patch] => Ok(Self::new(*major, *minor, *patch)),
+            _ => Err(CliError::InvalidData(format!(
+                "版本号应包含 3 个部分: {}",
+                value
+            ))),
+        }
+    }
+
+    pub fn to_value(self) -> Value {
+        Value::Array(<[u32; 3]>::from(self).iter().map(|v| Value::from(*v)).collect())
+    }
+
+    pub fn bump_major(self) -> Self {
+        Self::new(self.major + 1, 0, 0)
+    }
+
+    pub fn bump_minor(self) -> Self {
+        Self::new(self.major, self.minor + 1, 0)
+    }
+
+    pub fn bump_patch(self) -> Self {
+        Self::new(self.major, self.minor, self.patch + 1)
+    }
+}
+
+impl FromStr for Version {
+    type Err = CliError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            CliError::InvalidInput(format!(
+                "无效的版本号 '{}', 格式应为 major.minor.patch, 例如 1.2.3",
+                s
+            ))
+        };
+        let parts: Vec<u32> = s
+            .trim()
+            .split('.')
+            .map(|part| {
+                if part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(invalid());
+                }
+                part.parse::<u32>().map_err(|_| invalid())
+            })
+            .collect::<Result<_>>()?;
+        match parts.as_slice() {
+            [major, minor, patch] => Ok(Self::new(*major, *minor, *patch)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl From<[u32; 3]> for Version {
+    fn from([major, minor, patch]: [u32; 3]) -> Self {
+        Self::new(major, minor, patch)
+    }
+}
+
+impl From<Version> for [u32; 3] {
+    fn from(version: Version) -> Self {
+        [version.major, version.minor, version.patch]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_three_numeric_parts() {
+        assert_eq!("1.2.3".parse::<Version>().unwrap(), Version::new(1, 2, 3));
+        assert_eq!(" 0.10.0\n".parse::<Version>().unwrap(), Version::new(0, 10, 0));
+    }
+
+    #[test]
+    fn rejects_malformed_versions() {
+        let inputs = [
+            "", "1", "1.2", "1.2.3.4", "1..3", "1.2.", ".1.2", "+1.2.3", "1.-2.3", "1. 2.3", "a.b.c",
+            "4294967296.0.0",
+        ];
+        for input in inputs {
+            let result = input.parse::<Version>();
+            assert!(
+                matches!(result, Err(CliError::InvalidInput(_))),
+                "'{}' should be rejected, got {:?}",
+                input,
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn bumps_reset_lower_parts() {
+        let version = Version::new(1, 2, 3);
+        assert_eq!(version.bump_major(), Version::new(2, 0, 0));
+        assert_eq!(version.bump_minor(), Version::new(1, 3, 0));
+        assert_eq!(version.bump_patch(), Version::new(1, 2, 4));
+    }
+
+    #[test]
+    fn round_trips_through_json_arrays() {
+        let version = Version::new(1, 20, 0);
+        assert_eq!(version.to_value(), serde_json::json!([1, 20, 0]));
+        assert_eq!(Version::from_value(&version.to_value()).unwrap(), version);
+        assert!(Version::from_value(&serde_json::json!([1, 2])).is_err());
+        assert!(Version::from_value(&serde_json::json!("1.2.3")).is_err());
+    }
+}
@@ -16,6 +16,9 @@ fn main() {
         Commands::Release(args) => commands::release::execute(args),
         Commands::Create(args) => commands::create::execute(args, &temp_dir),
         Commands::Components(args) => commands::components::execute(args),
+        Commands::Template(args) => commands::template::execute(args, &temp_dir),
+        Commands::Manifest(args) => commands::manifest::execute(args),
+        Commands::Info(args) => commands::info::execute(args),
     }
 }
 
@@ -0,0 +1,218 @@
+use crate::error::{CliError, Result};
+use crate::utils::{file, git};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const META_FILE: &str = "cache.json";
+const REPO_DIR: &str = "repo";
+const DEFAULT_REF: &str = "HEAD";
+
+/// 缓存条目元数据, 与检出的仓库一同保存在 `<缓存目录>/<key>/` 下
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CacheEntry {
+    pub repo_url: String,
+    pub git_ref: Option<String>,
+    pub commit: String,
+    pub updated_at: u64,
+    #[serde(skip)]
+    pub dir: PathBuf,
+}
+
+impl CacheEntry {
+    pub fn repo_dir(&self) -> PathBuf {
+        self.dir.join(REPO_DIR)
+    }
+}
+
+/// 以仓库地址和 git 引用为键的持久化模板缓存
+pub struct TemplateCache {
+    root: PathBuf,
+}
+
+impl TemplateCache {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn contains(&self, repo_url: &str, git_ref: Option<&str>) -> bool {
+        let entry_dir = self.root.join(cache_key(repo_url, git_ref));
+        git::is_repository(&entry_dir.join(REPO_DIR))
+    }
+
+    /// 获取指定引用的仓库检出目录, 网络不可用时回退到已有缓存
+    pub fn checkout(&self, repo_url: &str, git_ref: Option<&str>) -> Result<CacheEntry> {
+        let entry_dir = self.root.join(cache_key(repo_url, git_ref));
+        let repo_dir = entry_dir.join(REPO_DIR);
+
+        if git::is_repository(&repo_dir) {
+            if let Some(commit) = pinned_commit(&repo_dir, git_ref) {
+                println!("使用缓存模板: {} ({})", repo_url, short_commit(&commit));
+                return self.save_entry(&entry_dir, repo_url, git_ref, commit);
+            }
+            update_checkout(&repo_dir, git_ref)?;
+        } else {
+            let _ = fs::remove_dir_all(&entry_dir);
+            fs::create_dir_all(&entry_dir)?;
+            let cloned = git::clone_repository(repo_url, &repo_dir).and_then(|_| match git_ref {
+                Some(git_ref) => checkout_ref(&repo_dir, git_ref),
+                None => Ok(()),
+            });
+            // 克隆或切换引用失败时不保留停在默认分支上的仓库, 避免之后被当作该引用的缓存
+            if let Err(e) = cloned {
+                let _ = fs::remove_dir_all(&entry_dir);
+                return Err(e);
+            }
+        }
+
+        let commit = git::rev_parse(&repo_dir, DEFAULT_REF).ok_or_else(|| {
+            CliError::InvalidData(format!("无法解析模板仓库的提交: {}", repo_dir.display()))
+        })?;
+        self.save_entry(&entry_dir, repo_url, git_ref, commit)
+    }
+
+    /// 获取检出目录, 失败时回退到该仓库最近更新的缓存条目, 引用不存在时直接报错
+    pub fn checkout_or_cached(&self, repo_url: &str, git_ref: Option<&str>) -> Result<CacheEntry> {
+        match self.checkout(repo_url, git_ref) {
+            Ok(entry) => Ok(entry),
+            Err(e @ CliError::NotFound(_)) => Err(e),
+            Err(e) => {
+                let cached = self
+                    .list()?
+                    .into_iter()
+                    .find(|entry| entry.repo_url == repo_url && entry.git_ref.as_deref() == git_ref);
+                match cached {
+                    Some(entry) => {
+                        eprintln!("警告: {}, 使用缓存 ({})", e, short_commit(&entry.commit));
+                        Ok(entry)
+                    }
+                    None => Err(e),
+                }
+            }
+        }
+    }
+
+    pub fn list(&self) -> Result<Vec<CacheEntry>> {
+        let mut entries = Vec::new();
+        if !self.root.is_dir() {
+            return Ok(entries);
+        }
+        for dir in fs::read_dir(&self.root)? {
+            let dir = dir?.path();
+            let meta_path = dir.join(META_FILE);
+            if !meta_path.is_file() {
+                continue;
+            }
+            let json = file::read_file_to_json(&meta_path)?;
+            let mut entry: CacheEntry = serde_json::from_value(json)?;
+            entry.dir = dir;
+            entries.push(entry);
+        }
+        entries.sort_by_key(|e| std::cmp::Reverse(e.updated_at));
+        Ok(entries)
+    }
+
+    /// 删除缓存条目, 指定 `older_than_days` 时只删除超过该天数未更新的条目
+    pub fn prune(&self, older_than_days: Option<u64>) -> Result<Vec<CacheEntry>> {
+        let now = unix_now();
+        let mut removed = Vec::new();
+        for entry in self.list()? {
+            if let Some(days) = older_than_days
+                && now.saturating_sub(entry.updated_at) < days * 24 * 60 * 60
+            {
+                continue;
+            }
+            fs::remove_dir_all(&entry.dir)?;
+            removed.push(entry);
+        }
+        Ok(removed)
+    }
+
+    fn save_entry(
+        &self,
+        entry_dir: &Path,
+        repo_url: &str,
+        git_ref: Option<&str>,
+        commit: String,
+    ) -> Result<CacheEntry> {
+        let entry = CacheEntry {
+            repo_url: repo_url.to_string(),
+            git_ref: git_ref.map(|s| s.to_string()),
+            commit,
+            updated_at: unix_now(),
+            dir: entry_dir.to_path_buf(),
+        };
+        file::write_json_to_file(&entry_dir.join(META_FILE), &serde_json::to_value(&entry)?)?;
+        Ok(entry)
+    }
+}
+
+pub fn short_commit(commit: &str) -> &str {
+    &commit[..commit.len().min(8)]
+}
+
+/// 检出指定引用, 分支优先使用远程跟踪分支
+pub fn checkout_ref(repo_dir: &Path, git_ref: &str) -> Result<()> {
+    let remote_branch = format!("origin/{}", git_ref);
+    if git::rev_parse(repo_dir, &remote_branch).is_some() {
+        return git::checkout(repo_dir, &remote_branch);
+    }
+    if git::rev_parse(repo_dir, git_ref).is_none() {
+        return Err(CliError::NotFound(format!("模板引用 '{}' 不存在", git_ref)));
+    }
+    git::checkout(repo_dir, git_ref)
+}
+
+/// 拉取失败 (例如离线) 时继续使用已有缓存, 但引用不存在时报错
+fn update_checkout(repo_dir: &Path, git_ref: Option<&str>) -> Result<()> {
+    let updated = match git_ref {
+        None => git::pull(repo_dir),
+        Some(_) => git::fetch(repo_dir),
+    };
+    if let Err(e) = updated {
+        eprintln!("警告: 更新模板缓存失败, 使用已有缓存: {}", e);
+    }
+    match git_ref {
+        Some(git_ref) => checkout_ref(repo_dir, git_ref),
+        None => Ok(()),
+    }
+}
+
+/// 引用为提交哈希或标签且已检出时无需联网
+fn pinned_commit(repo_dir: &Path, git_ref: Option<&str>) -> Option<String> {
+    let git_ref = git_ref?;
+    let head = git::rev_parse(repo_dir, DEFAULT_REF)?;
+    let is_commit = git_ref.len() >= 7
+        && git_ref.chars().all(|c| c.is_ascii_hexdigit())
+        && head.starts_with(&git_ref.to_ascii_lowercase());
+    let is_tag = git::rev_parse(repo_dir, &format!("refs/tags/{}", git_ref))
+        .is_some_and(|tag| tag == head);
+    (is_commit || is_tag).then_some(head)
+}
+
+fn cache_key(repo_url: &str, git_ref: Option<&str>) -> String {
+    let slug = |s: &str| -> String {
+        s.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+            .collect()
+    };
+    let url = repo_url
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .split("://")
+        .last()
+        .unwrap_or(repo_url);
+    format!("{}@{}", slug(url), slug(git_ref.unwrap_or(DEFAULT_REF)))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
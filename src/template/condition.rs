@@ -0,0 +1,258 @@
+use crate::error::{CliError, Result};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// `{{#if cond}}`、`{{else}}`、`{{/if}}`, 独占一行时连同缩进和换行一起移除
+static BLOCK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)(^[ \t]*)?\{\{(#if\s+[^}]+|else|/if)\}\}([ \t]*\r?\n)?").unwrap()
+});
+
+/// 条件表达式: `var`、`!var`、`var == value` 或 `var != value`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Condition {
+    Truthy(String),
+    Not(String),
+    Equals(String, String),
+    NotEquals(String, String),
+}
+
+impl Condition {
+    pub fn parse(expr: &str) -> Result<Self> {
+        let expr = expr.trim();
+        let invalid = || CliError::InvalidData(format!("无效的条件表达式: '{}'", expr));
+
+        let condition = if let Some((key, value)) = expr.split_once("!=") {
+            Condition::NotEquals(parse_name(key).ok_or_else(invalid)?, parse_value(value))
+        } else if let Some((key, value)) = expr.split_once("==") {
+            Condition::Equals(parse_name(key).ok_or_else(invalid)?, parse_value(value))
+        } else if let Some(key) = expr.strip_prefix('!') {
+            Condition::Not(parse_name(key).ok_or_else(invalid)?)
+        } else {
+            Condition::Truthy(parse_name(expr).ok_or_else(invalid)?)
+        };
+        Ok(condition)
+    }
+
+    pub fn variable(&self) -> &str {
+        match self {
+            Condition::Truthy(key)
+            | Condition::Not(key)
+            | Condition::Equals(key, _)
+            | Condition::NotEquals(key, _) => key,
+        }
+    }
+
+    pub fn eval(&self, variables: &HashMap<String, String>) -> bool {
+        let value = variables.get(self.variable()).map(|s| s.as_str());
+        match self {
+            Condition::Truthy(_) => is_truthy(value),
+            Condition::Not(_) => !is_truthy(value),
+            Condition::Equals(_, expected) => value == Some(expected.as_str()),
+            Condition::NotEquals(_, expected) => value != Some(expected.as_str()),
+        }
+    }
+}
+
+/// 未设置、空字符串以及 `false`/`0`/`no`/`off` 视为假
+pub fn is_truthy(value: Option<&str>) -> bool {
+    match value {
+        Some(value) => !matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "" | "false" | "0" | "no" | "off"
+        ),
+        None => false,
+    }
+}
+
+/// 返回文本中条件块引用的全部条件
+pub fn find_conditions(text: &str) -> Result<Vec<Condition>> {
+    BLOCK_REGEX
+        .captures_iter(text)
+        .filter_map(|cap| cap[2].strip_prefix("#if").map(Condition::parse))
+        .collect()
+}
+
+/// 移除文本中的条件块标签, 保留所有分支的内容
+pub fn strip_tags(text: &str) -> String {
+    BLOCK_REGEX
+        .replace_all(text, |cap: &regex::Captures| {
+            format!(
+                "{}{}",
+                cap.get(1).map_or("", |m| m.as_str()),
+                cap.get(3).map_or("", |m| m.as_str())
+            )
+        })
+        .into_owned()
+}
+
+/// 展开文本中的条件块, 支持嵌套和 `{{else}}`
+pub fn render_blocks(text: &str, variables: &HashMap<String, String>) -> Result<String> {
+    struct Frame {
+        parent_active: bool,
+        value: bool,
+        in_else: bool,
+    }
+
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+    let is_active = |stack: &[Frame]| {
+        stack
+            .last()
+            .is_none_or(|f| f.parent_active && (f.value != f.in_else))
+    };
+
+    for cap in BLOCK_REGEX.captures_iter(text) {
+        let whole = cap.get(0).unwrap();
+        if is_active(&stack) {
+            output.push_str(&text[last..whole.start()]);
+        }
+        last = whole.end();
+
+        // 非独占一行时保留标签两侧的空白
+        let standalone = cap.get(1).is_some() && cap.get(3).is_some();
+        let leading = if standalone { "" } else { cap.get(1).map_or("", |m| m.as_str()) };
+        let trailing = if standalone { "" } else { cap.get(3).map_or("", |m| m.as_str()) };
+
+        if is_active(&stack) {
+            output.push_str(leading);
+        }
+
+        let tag = &cap[2];
+        if let Some(expr) = tag.strip_prefix("#if") {
+            let parent_active = is_active(&stack);
+            let value = Condition::parse(expr)?.eval(variables);
+            stack.push(Frame {
+                parent_active,
+                value,
+                in_else: false,
+            });
+        } else if tag == "else" {
+            match stack.last_mut() {
+                Some(frame) if !frame.in_else => frame.in_else = true,
+                _ => {
+                    return Err(CliError::InvalidData(
+                        "{{else}} 没有对应的 {{#if}}".to_string(),
+                    ));
+                }
+            }
+        } else if stack.pop().is_none() {
+            return Err(CliError::InvalidData(
+                "{{/if}} 没有对应的 {{#if}}".to_string(),
+            ));
+        }
+
+        if is_active(&stack) {
+            output.push_str(trailing);
+        }
+    }
+
+    if !stack.is_empty() {
+        return Err(CliError::InvalidData("{{#if}} 缺少对应的 {{/if}}".to_string()));
+    }
+    output.push_str(&text[last..]);
+    Ok(output)
+}
+
+fn parse_name(name: &str) -> Option<String> {
+    let name = name.trim();
+    let valid = !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    valid.then(|| name.to_string())
+}
+
+fn parse_value(value: &str) -> String {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn render(text: &str, pairs: &[(&str, &str)]) -> String {
+        render_blocks(text, &vars(pairs)).unwrap()
+    }
+
+    #[test]
+    fn parses_condition_expressions() {
+        assert_eq!(Condition::parse(" ui ").unwrap(), Condition::Truthy("ui".into()));
+        assert_eq!(Condition::parse("!ui").unwrap(), Condition::Not("ui".into()));
+        assert_eq!(
+            Condition::parse("kind == \"block\"").unwrap(),
+            Condition::Equals("kind".into(), "block".into())
+        );
+        assert_eq!(
+            Condition::parse("kind != 'item'").unwrap(),
+            Condition::NotEquals("kind".into(), "item".into())
+        );
+        assert!(Condition::parse("").is_err());
+        assert!(Condition::parse("a b").is_err());
+        assert!(Condition::parse("== x").is_err());
+    }
+
+    #[test]
+    fn removes_standalone_tags_with_their_lines() {
+        let text = "a\n  {{#if ui}}\n  b\n  {{else}}\n  c\n  {{/if}}\nd\n";
+        assert_eq!(render(text, &[("ui", "true")]), "a\n  b\nd\n");
+        assert_eq!(render(text, &[("ui", "false")]), "a\n  c\nd\n");
+        assert_eq!(render("a\r\n{{#if ui}}\r\nb\r\n{{/if}}\r\nc", &[("ui", "1")]), "a\r\nb\r\nc");
+    }
+
+    #[test]
+    fn keeps_whitespace_around_inline_tags() {
+        let text = "name = {{#if ui}}with_ui{{else}}plain{{/if}} end\n";
+        assert_eq!(render(text, &[("ui", "yes")]), "name = with_ui end\n");
+        assert_eq!(render(text, &[]), "name = plain end\n");
+        assert_eq!(render("a {{#if ui}} b {{/if}} c", &[("ui", "on")]), "a  b  c");
+    }
+
+    #[test]
+    fn renders_nested_blocks() {
+        let text = "{{#if a}}\nA\n{{#if b}}\nAB\n{{else}}\nA!B\n{{/if}}\n{{else}}\n{{#if b}}\n!AB\n{{/if}}\nnone\n{{/if}}\n";
+        assert_eq!(render(text, &[("a", "1"), ("b", "1")]), "A\nAB\n");
+        assert_eq!(render(text, &[("a", "1")]), "A\nA!B\n");
+        assert_eq!(render(text, &[("b", "1")]), "!AB\nnone\n");
+        assert_eq!(render(text, &[]), "none\n");
+    }
+
+    #[test]
+    fn evaluates_comparisons_and_falsy_values() {
+        let text = "{{#if kind == block}}B{{/if}}{{#if kind != block}}O{{/if}}";
+        assert_eq!(render(text, &[("kind", "block")]), "B");
+        assert_eq!(render(text, &[("kind", "item")]), "O");
+        for value in ["", "false", "FALSE", "0", "no", "off"] {
+            assert_eq!(render("{{#if x}}y{{/if}}", &[("x", value)]), "", "value '{}'", value);
+        }
+    }
+
+    #[test]
+    fn rejects_unbalanced_tags() {
+        for text in [
+            "{{#if a}}x",
+            "x{{/if}}",
+            "{{else}}",
+            "{{#if a}}x{{else}}y{{else}}z{{/if}}",
+            "{{#if a}}{{#if b}}{{/if}}",
+            "{{#if a b}}x{{/if}}",
+        ] {
+            assert!(render_blocks(text, &HashMap::new()).is_err(), "'{}' should be rejected", text);
+        }
+    }
+
+    #[test]
+    fn strip_tags_keeps_all_branches() {
+        let text = "{{#if a}}\nA\n{{else}}\nB\n{{/if}}\n";
+        assert_eq!(strip_tags(text), "\nA\n\nB\n\n");
+        assert_eq!(find_conditions(text).unwrap(), vec![Condition::Truthy("a".into())]);
+    }
+}
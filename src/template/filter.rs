@@ -0,0 +1,93 @@
+/// 对变量值依次应用 `|` 分隔的过滤器, 存在未知过滤器时返回 `None`
+pub fn apply_filters(value: &str, filters: &str) -> Option<String> {
+    filters
+        .split('|')
+        .filter(|name| !name.is_empty())
+        .try_fold(value.to_string(), |value, name| apply_filter(&value, name))
+}
+
+pub fn apply_filter(value: &str, name: &str) -> Option<String> {
+    let result = match name {
+        "lower" => value.to_lowercase(),
+        "upper" => value.to_uppercase(),
+        "snake" => join_words(value, "_", Case::Lower),
+        "kebab" => join_words(value, "-", Case::Lower),
+        "constant" => join_words(value, "_", Case::Upper),
+        "dot" => join_words(value, ".", Case::Lower),
+        "pascal" => join_words(value, "", Case::Title),
+        "camel" => lower_first(&join_words(value, "", Case::Title)),
+        "lower_first" => lower_first(value),
+        "upper_first" => upper_first(value),
+        "short8" => value.chars().take(8).collect(),
+        "trim" => value.trim().to_string(),
+        _ => return None,
+    };
+    Some(result)
+}
+
+#[derive(Clone, Copy)]
+enum Case {
+    Lower,
+    Upper,
+    Title,
+}
+
+fn join_words(value: &str, separator: &str, case: Case) -> String {
+    split_words(value)
+        .iter()
+        .map(|word| match case {
+            Case::Lower => word.to_lowercase(),
+            Case::Upper => word.to_uppercase(),
+            Case::Title => upper_first(&word.to_lowercase()),
+        })
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// 按非字母数字字符和大小写边界拆分单词, 连续大写视为一个缩写,
+/// 例如 `HTTPServer_v2` 拆分为 `HTTP`, `Server`, `v2`
+fn split_words(value: &str) -> Vec<String> {
+    let chars: Vec<char> = value.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if let Some(prev) = current.chars().last() {
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            let boundary = c.is_uppercase()
+                && (prev.is_lowercase()
+                    || prev.is_ascii_digit()
+                    || (prev.is_uppercase() && next_is_lower));
+            if boundary {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn lower_first(value: &str) -> String {
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn upper_first(value: &str) -> String {
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,167 @@
+use crate::error::{CliError, Result};
+use crate::utils::{file, git, prompt};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path};
+use std::process::Command;
+
+/// 生成完成后执行的操作, 路径均相对于项目目录
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Hook {
+    #[serde(flatten)]
+    pub action: HookAction,
+    #[serde(default)]
+    pub when: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum HookAction {
+    GitInit,
+    CreateFile {
+        path: String,
+        #[serde(default)]
+        content: String,
+        #[serde(default)]
+        overwrite: bool,
+    },
+    CopyFile {
+        from: String,
+        to: String,
+    },
+    Shell {
+        command: String,
+    },
+}
+
+/// 控制 `shell` 操作是否需要确认
+#[derive(Debug, Clone, Copy)]
+pub struct HookPolicy {
+    pub trust_template: bool,
+    pub no_input: bool,
+}
+
+impl HookAction {
+    pub fn describe(&self) -> String {
+        match self {
+            HookAction::GitInit => "git init".to_string(),
+            HookAction::CreateFile { path, .. } => format!("创建文件 {}", path),
+            HookAction::CopyFile { from, to } => format!("复制文件 {} -> {}", from, to),
+            HookAction::Shell { command } => format!("执行命令 {}", command),
+        }
+    }
+
+    /// 文件操作涉及的路径, 均应相对于项目目录
+    pub fn paths(&self) -> Vec<&str> {
+        match self {
+            HookAction::GitInit | HookAction::Shell { .. } => vec![],
+            HookAction::CreateFile { path, .. } => vec![path],
+            HookAction::CopyFile { from, to } => vec![from, to],
+        }
+    }
+
+    /// 文件操作只能作用于项目目录内部, 拒绝绝对路径和包含 `..` 的路径
+    pub fn check_paths(&self) -> Result<()> {
+        for path in self.paths() {
+            check_relative_path(path)?;
+        }
+        Ok(())
+    }
+
+    /// 在最终的项目目录 `dir` 中执行已完成变量替换的操作
+    pub fn run(&self, dir: &Path, policy: HookPolicy) -> Result<()> {
+        match self {
+            HookAction::GitInit => {
+                if git::is_repository(dir) {
+                    println!(" - 跳过 git init: 已是 git 仓库");
+                    return Ok(());
+                }
+                git::init(dir)?;
+            }
+            HookAction::CreateFile {
+                path,
+                content,
+                overwrite,
+            } => {
+                let target = dir.join(path);
+                if target.exists() && !overwrite {
+                    println!(" - 跳过创建文件: {} 已存在", path);
+                    return Ok(());
+                }
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&target, content)?;
+            }
+            HookAction::CopyFile { from, to } => {
+                let source = dir.join(from);
+                let target = dir.join(to);
+                if source.is_dir() {
+                    file::copy_folder(&source, &target)?;
+                } else {
+                    if let Some(parent) = target.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::copy(&source, &target).map_err(|e| {
+                        CliError::NotFound(format!("无法复制 {}: {}", source.display(), e))
+                    })?;
+                }
+            }
+            HookAction::Shell { command } => {
+                if !policy.trust_template {
+                    if policy.no_input {
+                        eprintln!(
+                            "警告: 跳过模板命令 '{}', 使用 --trust-template 允许执行",
+                            command
+                        );
+                        return Ok(());
+                    }
+                    let message = format!("模板请求执行命令 '{}', 是否允许?", command);
+                    if !prompt::confirm(&message)? {
+                        println!(" - 已跳过: {}", command);
+                        return Ok(());
+                    }
+                }
+                run_shell(dir, command)?;
+            }
+        }
+        println!(" - {}", self.describe());
+        Ok(())
+    }
+}
+
+fn check_relative_path(path: &str) -> Result<()> {
+    let escapes = Path::new(path).components().any(|component| {
+        matches!(
+            component,
+            Component::ParentDir | Component::RootDir | Component::Prefix(_)
+        )
+    });
+    if escapes || path.starts_with(['/', '\\']) {
+        return Err(CliError::InvalidInput(format!(
+            "模板操作的路径 '{}' 必须位于项目目录内, 不能为绝对路径或包含 '..'",
+            path
+        )));
+    }
+    Ok(())
+}
+
+fn run_shell(dir: &Path, command: &str) -> Result<()> {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C");
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c");
+        cmd
+    };
+    let status = cmd.arg(command).current_dir(dir).status()?;
+    if !status.success() {
+        return Err(CliError::InvalidData(format!(
+            "模板命令 '{}' 执行失败: {}",
+            command, status
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,243 @@
+use crate::error::Result;
+use crate::template::condition::{self, Condition};
+use crate::template::hook::HookAction;
+use crate::template::{BUILTIN_VARIABLES, TemplateEngine, filter};
+use crate::utils::glob::{Glob, to_slash_path};
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug)]
+pub struct Issue {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.severity {
+            Severity::Error => "错误",
+            Severity::Warning => "警告",
+        };
+        write!(f, "[{}] {}", label, self.message)
+    }
+}
+
+#[derive(Default)]
+struct Linter {
+    issues: Vec<Issue>,
+    /// 变量名 -> 首次使用的位置
+    used: BTreeMap<String, String>,
+}
+
+impl Linter {
+    fn error(&mut self, message: String) {
+        self.issues.push(Issue {
+            severity: Severity::Error,
+            message,
+        });
+    }
+
+    fn warning(&mut self, message: String) {
+        self.issues.push(Issue {
+            severity: Severity::Warning,
+            message,
+        });
+    }
+
+    fn use_variable(&mut self, name: &str, location: &str) {
+        self.used
+            .entry(name.to_string())
+            .or_insert_with(|| location.to_string());
+    }
+
+    fn check_placeholders(&mut self, engine: &TemplateEngine, ext: Option<&str>, text: &str, location: &str) {
+        for (name, filters) in engine.find_placeholders(ext, text) {
+            self.use_variable(&name, location);
+            for filter_name in filters.split('|').filter(|f| !f.is_empty()) {
+                if filter::apply_filter("", filter_name).is_none() {
+                    self.error(format!("{}: 未知的过滤器 '{}'", location, filter_name));
+                }
+            }
+        }
+    }
+
+    fn check_condition(&mut self, expr: &str, location: &str) {
+        match Condition::parse(expr) {
+            Ok(condition) => self.use_variable(condition.variable(), location),
+            Err(e) => self.error(format!("{}: {}", location, e)),
+        }
+    }
+}
+
+/// 静态检查模板, 不修改任何文件
+pub fn validate(template_dir: &Path) -> Result<Vec<Issue>> {
+    let engine = TemplateEngine::load(template_dir)?;
+    let config = engine.config();
+    let mut linter = Linter::default();
+
+    let mut keys: Vec<&String> = config.variables.keys().collect();
+    keys.sort();
+    for key in keys {
+        let var = &config.variables[key];
+        let location = format!("变量 {}", key);
+        if let Some(default) = var.default_text() {
+            linter.check_placeholders(&engine, None, &default, &location);
+            // 引用其他变量的默认值只能在生成时校验
+            if !default.contains("{{") {
+                for error in var.check(key, &default) {
+                    linter.error(format!("默认值无效: {}", error));
+                }
+            }
+        }
+        if let Some(pattern) = &var.pattern
+            && let Err(e) = Regex::new(pattern)
+        {
+            linter.error(format!("{}: 无效的 pattern: {}", location, e));
+        }
+    }
+
+    check_renames(&engine, template_dir, &mut linter);
+
+    for (label, rules) in [("include", &config.include), ("exclude", &config.exclude)] {
+        for rule in rules {
+            let location = format!("[[{}]]", label);
+            if let Some(when) = &rule.when {
+                linter.check_condition(when, &location);
+            }
+            for pattern in &rule.paths {
+                if let Err(e) = Glob::new(pattern) {
+                    linter.error(format!("{}: {}", location, e));
+                }
+            }
+        }
+    }
+
+    for hook in &config.hooks {
+        let location = format!("[[hooks]] {}", hook.action.describe());
+        if let Some(when) = &hook.when {
+            linter.check_condition(when, &location);
+        }
+        let texts: Vec<&str> = match &hook.action {
+            HookAction::GitInit => vec![],
+            HookAction::CreateFile { path, content, .. } => vec![path, content],
+            HookAction::CopyFile { from, to } => vec![from, to],
+            HookAction::Shell { command } => vec![command],
+        };
+        for text in texts {
+            linter.check_placeholders(&engine, None, text, &location);
+        }
+        if let Err(e) = hook.action.check_paths() {
+            linter.error(format!("{}: {}", location, e));
+        }
+    }
+
+    check_files(&engine, template_dir, &mut linter)?;
+
+    for (name, location) in linter.used.clone() {
+        if !config.variables.contains_key(&name) && !BUILTIN_VARIABLES.contains(&name.as_str()) {
+            linter.error(format!("{}: 使用了未声明的变量 '{}'", location, name));
+        }
+    }
+    let mut unused: Vec<&String> = config
+        .variables
+        .keys()
+        .filter(|key| !linter.used.contains_key(*key))
+        .collect();
+    unused.sort();
+    for key in unused {
+        linter.warning(format!("变量 '{}' 已声明但从未使用", key));
+    }
+
+    linter.issues.sort_by_key(|issue| issue.severity);
+    Ok(linter.issues)
+}
+
+fn check_renames(engine: &TemplateEngine, template_dir: &Path, linter: &mut Linter) {
+    let renames = &engine.config().renames;
+    let mut seen_from: HashMap<&str, usize> = HashMap::new();
+    let mut seen_to: HashMap<&str, usize> = HashMap::new();
+
+    for (index, rule) in renames.iter().enumerate() {
+        let location = format!("renames[{}] {} -> {}", index, rule.from, rule.to);
+        linter.check_placeholders(engine, None, &rule.to, &location);
+
+        let from = rule.from.trim_matches('/');
+        if !template_dir.join(from).exists() {
+            linter.error(format!("{}: 源路径不存在", location));
+        }
+        if let Some(first) = seen_from.insert(from, index) {
+            linter.error(format!("{}: 与 renames[{}] 的源路径重复", location, first));
+        }
+        let to = rule.to.trim_matches('/');
+        if let Some(first) = seen_to.insert(to, index) {
+            linter.error(format!("{}: 与 renames[{}] 的目标路径重复", location, first));
+        }
+    }
+
+    for (index, rule) in renames.iter().enumerate() {
+        let to = rule.to.trim_matches('/');
+        if let Some(&other) = seen_from.get(to)
+            && other != index
+        {
+            linter.error(format!(
+                "renames[{}] 的目标路径 {} 与 renames[{}] 的源路径重叠",
+                index, rule.to, other
+            ));
+        }
+    }
+}
+
+fn check_files(engine: &TemplateEngine, template_dir: &Path, linter: &mut Linter) -> Result<()> {
+    let mut unprocessed: BTreeSet<String> = BTreeSet::new();
+
+    for entry in WalkDir::new(template_dir).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !path.is_file() || path.components().any(|c| c.as_os_str() == ".git") {
+            continue;
+        }
+        let relative = to_slash_path(path.strip_prefix(template_dir).unwrap_or(path));
+        if relative == "template.toml" {
+            continue;
+        }
+        // 二进制文件无法按文本读取, 直接跳过
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+        let ext = path.extension().and_then(|s| s.to_str());
+
+        if ext.is_some_and(|ext| engine.is_processed_extension(ext)) {
+            linter.check_placeholders(engine, ext, &condition::strip_tags(&content), &relative);
+            match condition::find_conditions(&content) {
+                Ok(conditions) => {
+                    for condition in conditions {
+                        linter.use_variable(condition.variable(), &relative);
+                    }
+                }
+                Err(e) => linter.error(format!("{}: {}", relative, e)),
+            }
+            if let Err(e) = condition::render_blocks(&content, &HashMap::new()) {
+                linter.error(format!("{}: {}", relative, e));
+            }
+        } else if !engine.find_placeholders(None, &content).is_empty() {
+            unprocessed.insert(relative);
+        }
+    }
+
+    for relative in unprocessed {
+        linter.warning(format!(
+            "{}: 包含占位符, 但其扩展名不在 process.file_extensions 中, 不会被替换",
+            relative
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,589 @@
+pub mod cache;
+pub mod condition;
+pub mod filter;
+pub mod hook;
+pub mod lint;
+pub mod source;
+pub mod variable;
+
+pub use variable::VariableConfig;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+use regex::{Captures, Regex};
+use walkdir::WalkDir;
+use crate::utils::glob::{Glob, to_slash_path};
+use condition::Condition;
+use hook::{Hook, HookAction, HookPolicy};
+
+/// `create` 根据项目名称和 UUID 自动设置的变量, 模板无需声明, 也不能通过 `--var` 覆盖
+pub const BUILTIN_VARIABLES: &[&str] = &[
+    "mod_name",
+    "mod_name_lower",
+    "python_module",
+    "namespace",
+    "pack_name",
+    "behavior_pack_uuid",
+    "resource_pack_uuid",
+    "behavior_module_uuid",
+    "resource_module_uuid",
+    "behavior_pack_uuid_short",
+    "resource_pack_uuid_short",
+];
+
+/// `{{name}}` 或带过滤器的 `{{name|snake|upper}}`
+static PLACEHOLDER_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{(\w+)((?:\|\w+)*)\}\}").unwrap());
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TemplateConfig {
+    pub template: TemplateInfo,
+    #[serde(default)]
+    pub renames: Vec<RenameRule>,
+    pub variables: HashMap<String, VariableConfig>,
+    pub process: ProcessConfig,
+    #[serde(default)]
+    pub include: Vec<PathRule>,
+    #[serde(default)]
+    pub exclude: Vec<PathRule>,
+    #[serde(default)]
+    pub hooks: Vec<Hook>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TemplateInfo {
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RenameRule {
+    pub from: String,
+    pub to: String,
+}
+
+/// 按条件保留或删除的路径, `paths` 为相对模板根目录的通配符
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PathRule {
+    pub paths: Vec<String>,
+    #[serde(default)]
+    pub when: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ProcessConfig {
+    pub file_extensions: Vec<String>,
+    /// 按扩展名追加的占位符格式, 例如 `.py` 文件中的 `__mod_name__`
+    #[serde(default)]
+    pub placeholders: Vec<PlaceholderStyle>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PlaceholderStyle {
+    pub extensions: Vec<String>,
+    pub prefix: String,
+    pub suffix: String,
+}
+
+/// 编译后的额外占位符格式, 只匹配模板中声明或已设置的变量,
+/// 避免误伤 `__init__` 之类的同形文本
+struct ExtraPlaceholder {
+    extensions: Vec<String>,
+    regex: Regex,
+}
+
+/// `create --dry-run` 的生成结果预览
+#[derive(Debug, Default)]
+pub struct GenerationPlan {
+    pub files: Vec<String>,
+    pub removed: Vec<String>,
+    pub renames: Vec<(String, String)>,
+    /// 会被改写的文件及其中的占位符
+    pub rewrites: Vec<(String, Vec<String>)>,
+    pub hooks: Vec<String>,
+}
+
+pub struct TemplateEngine {
+    config: TemplateConfig,
+    variables: HashMap<String, String>,
+    extra_placeholders: Vec<ExtraPlaceholder>,
+}
+
+impl TemplateConfig {
+    pub fn load(template_dir: &Path) -> crate::error::Result<Self> {
+        let config_path = template_dir.join("template.toml");
+        let content = fs::read_to_string(&config_path)?;
+        Ok(toml::from_str(&content)?)
+    }
+}
+
+impl TemplateEngine {
+    pub fn load(template_dir: &Path) -> crate::error::Result<Self> {
+        let config = TemplateConfig::load(template_dir)?;
+
+        let extra_placeholders = config
+            .process
+            .placeholders
+            .iter()
+            .map(|style| {
+                if style.prefix.is_empty() || style.suffix.is_empty() {
+                    return Err(crate::error::CliError::InvalidData(
+                        "占位符格式的 prefix 和 suffix 不能为空".to_string(),
+                    ));
+                }
+                let pattern = format!(
+                    r"{}(\w+?)((?:\|\w+)*){}",
+                    regex::escape(&style.prefix),
+                    regex::escape(&style.suffix)
+                );
+                Ok(ExtraPlaceholder {
+                    extensions: style.extensions.clone(),
+                    regex: Regex::new(&pattern).map_err(|e| {
+                        crate::error::CliError::InvalidData(format!("无效的占位符格式: {}", e))
+                    })?,
+                })
+            })
+            .collect::<crate::error::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            config,
+            variables: HashMap::new(),
+            extra_placeholders,
+        })
+    }
+
+    pub fn set_variable(&mut self, key: String, value: String) {
+        self.variables.insert(key, value);
+    }
+
+    /// 为未设置的变量填充默认值, 默认值中引用的变量需先被设置,
+    /// 因此按依赖顺序反复处理直到没有新的变量被设置
+    pub fn apply_defaults(&mut self) {
+        loop {
+            let mut resolved = Vec::new();
+            for (key, var_config) in &self.config.variables {
+                if self.variables.contains_key(key) {
+                    continue;
+                }
+                let Some(default) = var_config.default_text() else {
+                    continue;
+                };
+                let ready = PLACEHOLDER_REGEX
+                    .captures_iter(&default)
+                    .all(|cap| self.variables.contains_key(&cap[1]));
+                if ready {
+                    resolved.push((key.clone(), self.replace_placeholders(&default)));
+                }
+            }
+            if resolved.is_empty() {
+                break;
+            }
+            self.variables.extend(resolved);
+        }
+    }
+
+    /// 返回尚未设置的必需变量及其配置, 按变量名排序
+    pub fn missing_variables(&self) -> Vec<(String, VariableConfig)> {
+        let mut missing: Vec<(String, VariableConfig)> = self
+            .config
+            .variables
+            .iter()
+            .filter(|(key, var_config)| var_config.required && !self.variables.contains_key(*key))
+            .map(|(key, var_config)| (key.clone(), var_config.clone()))
+            .collect();
+        missing.sort_by(|a, b| a.0.cmp(&b.0));
+        missing
+    }
+
+    /// 检查所有变量, 一次性报告全部缺失或无效的值
+    pub fn validate_variables(&self) -> crate::error::Result<()> {
+        let mut keys: Vec<&String> = self.config.variables.keys().collect();
+        keys.sort();
+
+        let mut errors = Vec::new();
+        for key in keys {
+            let var_config = &self.config.variables[key];
+            match self.variables.get(key) {
+                Some(value) => errors.extend(var_config.check(key, value)),
+                None if var_config.required => errors.push(format!(
+                    "缺少必需的变量: {} ({})",
+                    key, var_config.description
+                )),
+                None => {}
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(crate::error::CliError::InvalidInput(format!(
+                "模板变量校验失败:\n  - {}",
+                errors.join("\n  - ")
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn process_directory(&self, dir: &Path) -> crate::error::Result<()> {
+        self.validate_variables()?;
+
+        self.remove_excluded_paths(dir)?;
+
+        self.replace_in_files(dir)?;
+
+        self.apply_renames(dir)?;
+
+        self.verify_no_placeholders(dir)?;
+
+        Ok(())
+    }
+
+    /// 按声明顺序在项目目录中执行生成后操作, 操作参数中的占位符会先被替换
+    pub fn run_hooks(&self, dir: &Path, policy: HookPolicy) -> crate::error::Result<()> {
+        for hook in &self.config.hooks {
+            if let Some(expr) = &hook.when
+                && !Condition::parse(expr)?.eval(&self.variables)
+            {
+                continue;
+            }
+            self.render_hook(&hook.action)?.run(dir, policy)?;
+        }
+        Ok(())
+    }
+
+    fn render_hook(&self, action: &HookAction) -> crate::error::Result<HookAction> {
+        let render = |text: &str| -> crate::error::Result<String> {
+            let rendered = condition::render_blocks(text, &self.variables)?;
+            Ok(self.replace_placeholders(&rendered))
+        };
+        let rendered = match action {
+            HookAction::GitInit => HookAction::GitInit,
+            HookAction::CreateFile {
+                path,
+                content,
+                overwrite,
+            } => HookAction::CreateFile {
+                path: render(path)?,
+                content: render(content)?,
+                overwrite: *overwrite,
+            },
+            HookAction::CopyFile { from, to } => HookAction::CopyFile {
+                from: render(from)?,
+                to: render(to)?,
+            },
+            HookAction::Shell { command } => HookAction::Shell {
+                command: render(command)?,
+            },
+        };
+        rendered.check_paths()?;
+        Ok(rendered)
+    }
+
+    fn replace_in_files(&self, dir: &Path) -> crate::error::Result<()> {
+        for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+            let path = entry.path();
+            
+            if !path.is_file() {
+                continue;
+            }
+
+            let Some(ext) = path.extension().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if !self.is_processed_extension(ext) {
+                continue;
+            }
+
+            let content = fs::read_to_string(path)?;
+            let updated = self.render_content(path, ext, &content)?;
+
+            if updated != content {
+                fs::write(path, updated)?;
+                println!(" - 处理文件: {}", path.display());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_content(&self, path: &Path, ext: &str, content: &str) -> crate::error::Result<String> {
+        let rendered = condition::render_blocks(content, &self.variables).map_err(|e| {
+            crate::error::CliError::InvalidData(format!("{}: {}", path.display(), e))
+        })?;
+        Ok(self.replace_extra_placeholders(ext, &self.replace_placeholders(&rendered)))
+    }
+
+    /// 在不写入磁盘的情况下计算生成结果, 路径均为相对项目根目录的 `/` 分隔路径
+    pub fn plan(&self, template_dir: &Path) -> crate::error::Result<GenerationPlan> {
+        self.validate_variables()?;
+
+        let globs = self.removal_globs()?;
+        let renames: Vec<(String, String)> = self
+            .sorted_renames()
+            .into_iter()
+            .filter(|rule| template_dir.join(&rule.from).exists())
+            .map(|rule| {
+                (
+                    rule.from.trim_matches('/').to_string(),
+                    self.replace_placeholders(&rule.to).trim_matches('/').to_string(),
+                )
+            })
+            .collect();
+
+        let mut plan = GenerationPlan::default();
+        let mut walker = WalkDir::new(template_dir).min_depth(1).into_iter();
+        while let Some(entry) = walker.next() {
+            let entry = entry?;
+            let path = entry.path();
+            let relative = to_slash_path(path.strip_prefix(template_dir).unwrap_or(path));
+            if globs.iter().any(|glob| glob.is_match(&relative)) {
+                if entry.file_type().is_dir() {
+                    walker.skip_current_dir();
+                }
+                plan.removed.push(relative);
+                continue;
+            }
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let target = renames.iter().fold(relative, |current, (from, to)| {
+                match current.strip_prefix(from.as_str()) {
+                    Some(rest) if rest.is_empty() || rest.starts_with('/') => format!("{}{}", to, rest),
+                    _ => current,
+                }
+            });
+
+            if let Some(ext) = path.extension().and_then(|s| s.to_str())
+                && self.is_processed_extension(ext)
+            {
+                let content = fs::read_to_string(path)?;
+                if self.render_content(path, ext, &content)? != content {
+                    let mut placeholders: Vec<String> = self
+                        .find_placeholders(Some(ext), &condition::strip_tags(&content))
+                        .into_iter()
+                        .map(|(name, filters)| format!("{}{}", name, filters))
+                        .collect();
+                    placeholders.sort();
+                    placeholders.dedup();
+                    plan.rewrites.push((target.clone(), placeholders));
+                }
+            }
+            plan.files.push(target);
+        }
+
+        plan.files.sort();
+        plan.rewrites.sort();
+        plan.renames = renames;
+        for hook in &self.config.hooks {
+            if let Some(expr) = &hook.when
+                && !Condition::parse(expr)?.eval(&self.variables)
+            {
+                continue;
+            }
+            plan.hooks.push(self.render_hook(&hook.action)?.describe());
+        }
+        Ok(plan)
+    }
+
+    fn removal_globs(&self) -> crate::error::Result<Vec<Glob>> {
+        let mut globs = Vec::new();
+        for (rule, remove_when) in self
+            .config
+            .include
+            .iter()
+            .map(|rule| (rule, false))
+            .chain(self.config.exclude.iter().map(|rule| (rule, true)))
+        {
+            let active = match &rule.when {
+                Some(expr) => Condition::parse(expr)?.eval(&self.variables),
+                None => true,
+            };
+            if active == remove_when {
+                for pattern in &rule.paths {
+                    globs.push(Glob::new(pattern)?);
+                    // `dir/**` 删除整个目录, 而不是只清空目录内容
+                    if let Some(dir) = pattern.trim().strip_suffix("/**") {
+                        globs.push(Glob::new(dir)?);
+                    }
+                }
+            }
+        }
+        Ok(globs)
+    }
+
+    /// 删除 `[[exclude]]` 条件成立或 `[[include]]` 条件不成立的文件和目录
+    fn remove_excluded_paths(&self, dir: &Path) -> crate::error::Result<()> {
+        let globs = self.removal_globs()?;
+        if globs.is_empty() {
+            return Ok(());
+        }
+
+        let mut removed = Vec::new();
+        let mut walker = WalkDir::new(dir).min_depth(1).into_iter();
+        while let Some(entry) = walker.next() {
+            let entry = entry?;
+            let relative = to_slash_path(entry.path().strip_prefix(dir).unwrap_or(entry.path()));
+            if globs.iter().any(|glob| glob.is_match(&relative)) {
+                if entry.file_type().is_dir() {
+                    walker.skip_current_dir();
+                }
+                removed.push((entry.path().to_path_buf(), relative));
+            }
+        }
+
+        for (path, relative) in removed {
+            if path.is_dir() {
+                fs::remove_dir_all(&path)?;
+            } else {
+                fs::remove_file(&path)?;
+            }
+            println!(" - 移除: {}", relative);
+        }
+
+        Ok(())
+    }
+
+    /// `from` 均指模板原始结构中的路径, 因此先重命名深层路径再重命名其父目录
+    fn sorted_renames(&self) -> Vec<&RenameRule> {
+        let mut renames: Vec<&RenameRule> = self.config.renames.iter().collect();
+        renames.sort_by_key(|rule| std::cmp::Reverse(rule.from.trim_matches('/').matches('/').count()));
+        renames
+    }
+
+    fn apply_renames(&self, dir: &Path) -> crate::error::Result<()> {
+        for rule in self.sorted_renames() {
+            let from_path = dir.join(&rule.from);
+            
+            if !from_path.exists() {
+                continue;
+            }
+
+            let to_path_str = self.replace_placeholders(&rule.to);
+            let to_path = dir.join(&to_path_str);
+
+            if let Some(parent) = to_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            fs::rename(&from_path, &to_path)?;
+            println!(" - 重命名: {} -> {}", rule.from, to_path_str);
+        }
+
+        Ok(())
+    }
+
+    /// 替换文本中的占位符, 未设置的变量或未知的过滤器保留原样
+    fn replace_placeholders(&self, text: &str) -> String {
+        PLACEHOLDER_REGEX
+            .replace_all(text, |cap: &Captures| {
+                self.variables
+                    .get(&cap[1])
+                    .and_then(|value| filter::apply_filters(value, &cap[2]))
+                    .unwrap_or_else(|| cap[0].to_string())
+            })
+            .into_owned()
+    }
+
+    /// 替换扩展名对应的额外格式占位符
+    fn replace_extra_placeholders(&self, ext: &str, text: &str) -> String {
+        let mut result = text.to_string();
+        for style in self.extra_placeholders_for(ext) {
+            result = style
+                .regex
+                .replace_all(&result, |cap: &Captures| {
+                    self.variables
+                        .get(&cap[1])
+                        .and_then(|value| filter::apply_filters(value, &cap[2]))
+                        .unwrap_or_else(|| cap[0].to_string())
+                })
+                .into_owned();
+        }
+        result
+    }
+
+    fn extra_placeholders_for<'a>(&'a self, ext: &'a str) -> impl Iterator<Item = &'a ExtraPlaceholder> {
+        self.extra_placeholders
+            .iter()
+            .filter(move |style| style.extensions.iter().any(|e| e == ext))
+    }
+
+    pub fn variables(&self) -> &HashMap<String, String> {
+        &self.variables
+    }
+
+    pub fn config(&self) -> &TemplateConfig {
+        &self.config
+    }
+
+    /// 返回文本中的占位符名称和过滤器, 额外格式只返回已声明的变量
+    pub fn find_placeholders(&self, ext: Option<&str>, text: &str) -> Vec<(String, String)> {
+        let mut found: Vec<(String, String)> = PLACEHOLDER_REGEX
+            .captures_iter(text)
+            .map(|cap| (cap[1].to_string(), cap[2].to_string()))
+            .collect();
+        if let Some(ext) = ext {
+            for style in self.extra_placeholders_for(ext) {
+                found.extend(
+                    style
+                        .regex
+                        .captures_iter(text)
+                        .filter(|cap| self.is_known_variable(&cap[1]))
+                        .map(|cap| (cap[1].to_string(), cap[2].to_string())),
+                );
+            }
+        }
+        found
+    }
+
+    fn is_known_variable(&self, name: &str) -> bool {
+        self.config.variables.contains_key(name) || self.variables.contains_key(name)
+    }
+
+    pub fn is_processed_extension(&self, ext: &str) -> bool {
+        self.config.process.file_extensions.iter().any(|e| e == ext)
+            || self.extra_placeholders_for(ext).next().is_some()
+    }
+
+    fn verify_no_placeholders(&self, dir: &Path) -> crate::error::Result<()> {
+        let mut found_placeholders = Vec::new();
+
+        for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+            let path = entry.path();
+            
+            if !path.is_file() {
+                continue;
+            }
+
+            let Some(ext) = path.extension().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if !self.is_processed_extension(ext) {
+                continue;
+            }
+
+            let content = fs::read_to_string(path)?;
+            
+            for cap in PLACEHOLDER_REGEX.captures_iter(&content) {
+                found_placeholders.push(format!("{}:{}", path.display(), &cap[0]));
+            }
+            for style in self.extra_placeholders_for(ext) {
+                for cap in style.regex.captures_iter(&content) {
+                    if self.is_known_variable(&cap[1]) {
+                        found_placeholders.push(format!("{}:{}", path.display(), &cap[0]));
+                    }
+                }
+            }
+        }
+
+        if !found_placeholders.is_empty() {
+            eprintln!("警告: 发现未替换的占位符:");
+            for placeholder in found_placeholders {
+                eprintln!("  - {}", placeholder);
+            }
+        }
+
+        Ok(())
+    }
+}
\ No newline at end of file
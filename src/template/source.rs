@@ -0,0 +1,215 @@
+use crate::config::Config;
+use crate::error::{CliError, Result};
+use crate::template::cache::{self, TemplateCache};
+use crate::utils::{file, git, http::HttpClient};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_EXAMPLE: &str = "default";
+const TEMPLATE_FILE: &str = "template.toml";
+
+/// 模板来源: 远程仓库中的示例、本地目录、本地 git 仓库或 zip 压缩包
+#[derive(Debug, Clone)]
+pub enum TemplateSource {
+    Remote { repo_url: String, example: String, git_ref: Option<String> },
+    LocalDir(PathBuf),
+    LocalGit { repo: PathBuf, example: Option<String>, git_ref: Option<String> },
+    Zip(PathBuf),
+}
+
+impl TemplateSource {
+    /// 解析 `--target` 参数, 本地路径优先, 否则视为远程仓库中的示例名称。
+    /// 本地目录和本地 git 仓库可以使用 `<路径>#<示例>` 指定 `examples/` 下的示例。
+    /// `git_ref` 仅对 git 来源生效。
+    pub fn parse(target: Option<&str>, git_ref: Option<&str>, config: &Config) -> Self {
+        let git_ref = git_ref.map(|s| s.to_string());
+        let target = target.unwrap_or(DEFAULT_EXAMPLE);
+
+        let (path_part, example) = match target.rsplit_once('#') {
+            Some((path, example)) if !example.is_empty() => (path, Some(example.to_string())),
+            _ => (target, None),
+        };
+        let path = PathBuf::from(path_part);
+
+        if path.is_file() && has_extension(&path, "zip") {
+            return TemplateSource::Zip(path);
+        }
+        if path.is_dir() {
+            if example.is_none() && path.join(TEMPLATE_FILE).is_file() {
+                return TemplateSource::LocalDir(path);
+            }
+            if git::is_repository(&path) {
+                return TemplateSource::LocalGit {
+                    repo: path,
+                    example,
+                    git_ref,
+                };
+            }
+            return match example {
+                Some(example) => TemplateSource::LocalDir(path.join("examples").join(example)),
+                None => TemplateSource::LocalDir(path),
+            };
+        }
+
+        TemplateSource::Remote {
+            repo_url: config.repo_url.clone(),
+            example: target.to_string(),
+            git_ref,
+        }
+    }
+
+    pub fn git_ref(&self) -> Option<&str> {
+        match self {
+            TemplateSource::Remote { git_ref, .. } | TemplateSource::LocalGit { git_ref, .. } => {
+                git_ref.as_deref()
+            }
+            _ => None,
+        }
+    }
+
+    /// 不含 git 引用的模板位置, 格式与 `--target` 相同
+    pub fn location(&self) -> String {
+        match self {
+            TemplateSource::Remote { repo_url, example, .. } => format!("{}#{}", repo_url, example),
+            TemplateSource::LocalDir(path) | TemplateSource::Zip(path) => path.display().to_string(),
+            TemplateSource::LocalGit { repo, example, .. } => match example {
+                Some(example) => format!("{}#{}", repo.display(), example),
+                None => repo.display().to_string(),
+            },
+        }
+    }
+
+    /// 准备模板文件, 返回包含 `template.toml` 的模板目录
+    pub fn fetch(&self, temp_dir: &Path, cache: &TemplateCache) -> Result<PathBuf> {
+        let template_dir = match self {
+            TemplateSource::Remote { repo_url, example, git_ref } => {
+                if !cache.contains(repo_url, git_ref.as_deref()) {
+                    match check_example_exists(repo_url, example, git_ref.as_deref()) {
+                        Err(CliError::Network(e)) => eprintln!("警告: 无法检查示例模板: {}", e),
+                        result => result?,
+                    }
+                }
+                let entry = cache.checkout(repo_url, git_ref.as_deref())?;
+                let template_dir = entry.repo_dir().join("examples").join(example);
+                if !template_dir.is_dir() {
+                    return Err(CliError::NotFound(format!(
+                        "示例模板 '{}' 不存在 ({})",
+                        example,
+                        cache::short_commit(&entry.commit)
+                    )));
+                }
+                template_dir
+            }
+            TemplateSource::LocalDir(path) => path.clone(),
+            TemplateSource::LocalGit { repo, example, git_ref } => {
+                let checkout = temp_dir.join("local_git");
+                let _ = fs::remove_dir_all(&checkout);
+                git::clone_repository(&repo.to_string_lossy(), &checkout)?;
+                if let Some(git_ref) = git_ref {
+                    cache::checkout_ref(&checkout, git_ref)?;
+                }
+                match example {
+                    Some(example) => checkout.join("examples").join(example),
+                    None if checkout.join(TEMPLATE_FILE).is_file() => checkout,
+                    None => checkout.join("examples").join(DEFAULT_EXAMPLE),
+                }
+            }
+            TemplateSource::Zip(archive) => {
+                let extract_dir = temp_dir.join("zip");
+                let _ = fs::remove_dir_all(&extract_dir);
+                file::extract_zip(archive, &extract_dir)?;
+                find_template_root(&extract_dir)?
+            }
+        };
+
+        if !template_dir.join(TEMPLATE_FILE).is_file() {
+            return Err(CliError::NotFound(format!(
+                "模板目录中缺少 {}: {}",
+                TEMPLATE_FILE,
+                template_dir.display()
+            )));
+        }
+
+        Ok(template_dir)
+    }
+}
+
+impl std::fmt::Display for TemplateSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.location())?;
+        match self.git_ref() {
+            Some(git_ref) => write!(f, " @ {}", git_ref),
+            None => Ok(()),
+        }
+    }
+}
+
+fn has_extension(path: &Path, ext: &str) -> bool {
+    path.extension()
+        .and_then(|s| s.to_str())
+        .is_some_and(|s| s.eq_ignore_ascii_case(ext))
+}
+
+/// 压缩包可能直接包含模板文件, 也可能包裹在单个顶层目录中
+fn find_template_root(dir: &Path) -> Result<PathBuf> {
+    if dir.join(TEMPLATE_FILE).is_file() {
+        return Ok(dir.to_path_buf());
+    }
+    let entries: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .collect();
+    if let [single] = entries.as_slice()
+        && single.is_dir()
+        && single.join(TEMPLATE_FILE).is_file()
+    {
+        return Ok(single.clone());
+    }
+    Err(CliError::NotFound(format!(
+        "压缩包中未找到 {}",
+        TEMPLATE_FILE
+    )))
+}
+
+/// `https://github.com/<owner>/<repo>` 形式的仓库地址对应的 GitHub API 路径 `<owner>/<repo>`
+fn github_repo_path(repo_url: &str) -> Option<&str> {
+    let path = repo_url
+        .strip_prefix("https://github.com/")?
+        .trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    match path.split('/').collect::<Vec<_>>().as_slice() {
+        [owner, repo] if !owner.is_empty() && !repo.is_empty() => Some(path),
+        _ => None,
+    }
+}
+
+/// 克隆前通过 GitHub API 提前检查示例是否存在, 其他托管平台的仓库在检出后再检查
+fn check_example_exists(repo_url: &str, target: &str, git_ref: Option<&str>) -> Result<()> {
+    let Some(repo_path) = github_repo_path(repo_url) else {
+        return Ok(());
+    };
+    let mut check_url = format!(
+        "https://api.github.com/repos/{}/contents/examples/{}",
+        repo_path, target
+    );
+    if let Some(git_ref) = git_ref {
+        check_url.push_str(&format!("?ref={}", git_ref));
+    }
+
+    let client = if cfg!(debug_assertions) {
+        HttpClient::new_with_proxy("http://127.0.0.1:1080")?
+    } else {
+        HttpClient::new()?
+    };
+
+    let resp = client.get(&check_url)?;
+
+    if !resp.status().is_success() {
+        return Err(CliError::NotFound(format!(
+            "示例模板 '{}' 不存在",
+            target
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct VariableConfig {
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub description: String,
+    /// 默认值, 字符串中可以引用其他变量, 例如 `"{{mod_name_lower}}"`
+    #[serde(default)]
+    pub default: Option<toml::Value>,
+    /// 值必须完整匹配的正则表达式
+    #[serde(default)]
+    pub pattern: Option<String>,
+    #[serde(default)]
+    pub choices: Vec<String>,
+    #[serde(default, rename = "type")]
+    pub var_type: VariableType,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VariableType {
+    #[default]
+    String,
+    Bool,
+    Int,
+    Uuid,
+}
+
+impl fmt::Display for VariableType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            VariableType::String => "string",
+            VariableType::Bool => "bool",
+            VariableType::Int => "int",
+            VariableType::Uuid => "uuid",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl VariableConfig {
+    /// 默认值的文本形式, 尚未替换其中引用的变量
+    pub fn default_text(&self) -> Option<String> {
+        self.default.as_ref().map(|value| match value {
+            toml::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+    }
+
+    /// 交互式询问时显示的提示
+    pub fn prompt(&self, key: &str) -> String {
+        let mut message = if self.description.is_empty() {
+            key.to_string()
+        } else {
+            format!("{} ({})", self.description, key)
+        };
+        if !self.choices.is_empty() {
+            message.push_str(&format!(" [{}]", self.choices.join("/")));
+        } else if self.var_type == VariableType::Bool {
+            message.push_str(" [true/false]");
+        }
+        message
+    }
+
+    /// 检查变量值, 返回所有不满足的约束
+    pub fn check(&self, key: &str, value: &str) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        let type_ok = match self.var_type {
+            VariableType::String => true,
+            VariableType::Bool => value == "true" || value == "false",
+            VariableType::Int => value.parse::<i64>().is_ok(),
+            VariableType::Uuid => Uuid::parse_str(value).is_ok(),
+        };
+        if !type_ok {
+            errors.push(format!("{} 的值 '{}' 不是有效的 {}", key, value, self.var_type));
+        }
+
+        if let Some(pattern) = &self.pattern {
+            match Regex::new(&format!("^(?:{})$", pattern)) {
+                Ok(regex) if !regex.is_match(value) => errors.push(format!(
+                    "{} 的值 '{}' 不匹配格式 {}",
+                    key, value, pattern
+                )),
+                Ok(_) => {}
+                Err(e) => errors.push(format!("{} 的格式 {} 无效: {}", key, pattern, e)),
+            }
+        }
+
+        if !self.choices.is_empty() && !self.choices.iter().any(|c| c == value) {
+            errors.push(format!(
+                "{} 的值 '{}' 不在可选值中: {}",
+                key,
+                value,
+                self.choices.join(", ")
+            ));
+        }
+
+        errors
+    }
+}
@@ -1,8 +1,9 @@
+use crate::entity::descriptor::DESCRIPTOR_FILE;
 use crate::error::Result;
 use serde_json::Value;
-use std::{fs, path::PathBuf};
+use std::{fs, path::{Component, Path, PathBuf}};
 
-pub fn copy_folder(src: &PathBuf, dest: &PathBuf) -> Result<()> {
+pub fn copy_folder(src: &Path, dest: &Path) -> Result<()> {
     if !src.exists() || !src.is_dir() {
         return Err(crate::error::CliError::NotFound(format!(
             "源目录不存在: {}",
@@ -25,19 +26,52 @@ pub fn copy_folder(src: &PathBuf, dest: &PathBuf) -> Result<()> {
     Ok(())
 }
 
-pub fn read_file_to_json(path: &PathBuf) -> Result<Value> {
+/// 返回 `src` 中会与 `dest` 已有内容冲突的相对路径
+pub fn find_conflicts(src: &Path, dest: &Path) -> Result<Vec<String>> {
+    let mut conflicts = Vec::new();
+    for entry in walkdir::WalkDir::new(src).min_depth(1) {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(src).unwrap_or(entry.path());
+        let target = dest.join(relative);
+        if !target.exists() {
+            continue;
+        }
+        let both_dirs = entry.file_type().is_dir() && target.is_dir();
+        if !both_dirs {
+            conflicts.push(relative.display().to_string());
+        }
+    }
+    Ok(conflicts)
+}
+
+/// 将 `src` 中的内容移动到 `dest`, 同名目录会被合并
+pub fn move_merge(src: &Path, dest: &Path) -> Result<()> {
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let src_path = entry.path();
+        let dest_path = dest.join(entry.file_name());
+        if dest_path.is_dir() && src_path.is_dir() {
+            move_merge(&src_path, &dest_path)?;
+        } else {
+            fs::rename(&src_path, &dest_path)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn read_file_to_json(path: &Path) -> Result<Value> {
     let file = fs::read_to_string(path)?;
     let json: Value = serde_json::from_str(&file)?;
     Ok(json)
 }
 
-pub fn write_json_to_file(path: &PathBuf, value: &Value) -> Result<()> {
+pub fn write_json_to_file(path: &Path, value: &Value) -> Result<()> {
     let content = serde_json::to_string_pretty(value)?;
     fs::write(path, content)?;
     Ok(())
 }
 
-pub fn update_json_file<F>(path: &PathBuf, updater: F) -> Result<()>
+pub fn update_json_file<F>(path: &Path, updater: F) -> Result<()>
 where
     F: FnOnce(&mut Value) -> Result<()>,
 {
@@ -47,7 +81,52 @@ where
     Ok(())
 }
 
+/// 标识项目根目录的文件
+const PROJECT_MARKERS: &[&str] = &[DESCRIPTOR_FILE, "world_behavior_packs.json"];
+
+/// 转换为绝对路径并去掉其中的 `.` 和 `..`,
+/// 不使用 `canonicalize` 以免在 Windows 上得到 `\\?\C:\...` 形式的路径
+pub fn absolute_path(path: &Path) -> Result<PathBuf> {
+    let mut absolute = PathBuf::new();
+    for component in std::path::absolute(path)?.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                absolute.pop();
+            }
+            component => absolute.push(component),
+        }
+    }
+    Ok(absolute)
+}
+
+/// 从 `--path` 或当前目录开始逐级向上查找项目根目录
 pub fn find_project_dir(path: &Option<String>) -> Result<PathBuf> {
-    let path = path.as_deref().unwrap_or(".");
-    Ok(PathBuf::from(path))
+    let start = PathBuf::from(path.as_deref().unwrap_or("."));
+    if !start.is_dir() {
+        return Err(crate::error::CliError::NotFound(format!("目录 {}", start.display())));
+    }
+    let start = absolute_path(&start)?;
+
+    let mut searched = Vec::new();
+    for dir in start.ancestors() {
+        if PROJECT_MARKERS.iter().any(|marker| dir.join(marker).is_file()) {
+            return Ok(dir.to_path_buf());
+        }
+        searched.push(dir.display().to_string());
+    }
+
+    Err(crate::error::CliError::NotFound(format!(
+        "项目根目录 (包含 {} 的目录), 已查找: {}",
+        PROJECT_MARKERS.join(" 或 "),
+        searched.join(", ")
+    )))
+}
+
+pub fn extract_zip(archive: &Path, dest: &Path) -> Result<()> {
+    let file = fs::File::open(archive)?;
+    let mut zip = zip::ZipArchive::new(file)?;
+    fs::create_dir_all(dest)?;
+    zip.extract(dest)?;
+    Ok(())
 }
@@ -1,11 +1,80 @@
-use std::path::PathBuf;
+use std::path::Path;
+use std::process::Command;
 use crate::error::Result;
 
-pub fn clone_remote_project(url: String, temp_dir: &PathBuf) -> Result<()> {
-    std::process::Command::new("git")
+pub fn clone_repository(url: &str, dest: &Path) -> Result<()> {
+    let output = Command::new("git")
         .arg("clone")
         .arg(url)
-        .arg(format!("{}/tmp", temp_dir.display()))
+        .arg(dest)
         .output()?;
+    if !output.status.success() {
+        return Err(crate::error::CliError::InvalidData(format!(
+            "git clone {} 失败: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn is_repository(path: &Path) -> bool {
+    path.join(".git").exists()
+}
+
+pub fn init(dir: &Path) -> Result<()> {
+    run(dir, &["init", "--quiet"])
+}
+
+pub fn fetch(repo: &Path) -> Result<()> {
+    run(repo, &["fetch", "--tags", "--force", "origin"])
+}
+
+pub fn pull(repo: &Path) -> Result<()> {
+    run(repo, &["pull", "--ff-only"])
+}
+
+pub fn checkout(repo: &Path, rev: &str) -> Result<()> {
+    run(repo, &["checkout", "--force", "--detach", rev])
+}
+
+/// 解析修订号为完整提交哈希, 不存在时返回 `None`
+pub fn rev_parse(repo: &Path, rev: &str) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["rev-parse", "--verify", "--quiet"])
+        .arg(format!("{}^{{commit}}", rev))
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// 最后一次提交的 Unix 时间戳, 不在 git 仓库中或没有提交时返回 `None`
+pub fn last_commit_time(repo: &Path) -> Option<i64> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["log", "-1", "--format=%ct"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+fn run(repo: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git").arg("-C").arg(repo).args(args).output()?;
+    if !output.status.success() {
+        return Err(crate::error::CliError::InvalidData(format!(
+            "git {} 失败: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use crate::error::{CliError, Result};
+use regex::Regex;
+
+/// 以 `/` 分隔的相对路径通配符, 支持 `*`、`?` 和跨目录的 `**`
+#[derive(Debug, Clone)]
+pub struct Glob {
+    regex: Regex,
+}
+
+impl Glob {
+    pub fn new(pattern: &str) -> Result<Self> {
+        let pattern = pattern.trim().trim_start_matches("./").to_string();
+        let mut regex = String::from("^");
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '*' if chars.get(i + 1) == Some(&'*') => {
+                    if chars.get(i + 2) == Some(&'/') {
+                        regex.push_str("(?:.*/)?");
+                        i += 3;
+                    } else {
+                        regex.push_str(".*");
+                        i += 2;
+                    }
+                    continue;
+                }
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                c => regex.push_str(&regex::escape(&c.to_string())),
+            }
+            i += 1;
+        }
+        regex.push('$');
+
+        let regex = Regex::new(&regex)
+            .map_err(|e| CliError::InvalidData(format!("无效的通配符 {}: {}", pattern, e)))?;
+        Ok(Self { regex })
+    }
+
+    /// `path` 为使用 `/` 分隔的相对路径
+    pub fn is_match(&self, path: &str) -> bool {
+        self.regex.is_match(path)
+    }
+}
+
+/// 将相对路径统一转换为 `/` 分隔
+pub fn to_slash_path(path: &std::path::Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_match(pattern: &str, path: &str) -> bool {
+        Glob::new(pattern).unwrap().is_match(path)
+    }
+
+    #[test]
+    fn single_star_stays_within_a_directory() {
+        assert!(is_match("*.json", "a.json"));
+        assert!(!is_match("*.json", "dir/a.json"));
+        assert!(is_match("dir/*.json", "dir/a.json"));
+        assert!(!is_match("dir/*.json", "dir/sub/a.json"));
+    }
+
+    #[test]
+    fn double_star_crosses_directories() {
+        assert!(is_match("**/a.json", "a.json"));
+        assert!(is_match("**/a.json", "x/y/a.json"));
+        assert!(is_match("dir/**", "dir/x/y.json"));
+        assert!(!is_match("dir/**", "dir"));
+        assert!(!is_match("dir/**", "other/dir/x"));
+    }
+
+    #[test]
+    fn question_mark_and_literals() {
+        assert!(is_match("a?.txt", "ab.txt"));
+        assert!(!is_match("a?.txt", "a/.txt"));
+        assert!(is_match("a+(1).txt", "a+(1).txt"));
+        assert!(is_match("./dir/a.txt", "dir/a.txt"));
+    }
+}
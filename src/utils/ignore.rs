@@ -0,0 +1,176 @@
+use crate::error::Result;
+use crate::utils::glob::Glob;
+use std::fs;
+use std::path::Path;
+
+pub const IGNORE_FILE: &str = ".emodignore";
+
+/// 打包时默认忽略的编辑器残留、源文件和缓存
+const DEFAULT_PATTERNS: &[&str] = &[
+    ".gitkeep",
+    ".git/",
+    ".DS_Store",
+    "Thumbs.db",
+    "desktop.ini",
+    ".idea/",
+    ".vscode/",
+    "*~",
+    "*.swp",
+    "*.swo",
+    "*.tmp",
+    "*.bak",
+    "*.psd",
+    "__pycache__/",
+    "*.pyc",
+    ".mcs/",
+];
+
+/// 一条 gitignore 风格的规则
+#[derive(Debug, Clone)]
+struct Rule {
+    glob: Glob,
+    negate: bool,
+    dir_only: bool,
+}
+
+/// gitignore 风格的忽略规则, 后出现的规则优先, `!` 开头的规则重新包含已忽略的路径
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    rules: Vec<Rule>,
+}
+
+impl IgnoreRules {
+    /// 依次加载内置规则、`emod.toml` 中的规则和项目根目录的 `.emodignore`
+    pub fn load(project_dir: &Path, extra_patterns: &[String]) -> Result<Self> {
+        let mut rules = Self::default();
+        for pattern in DEFAULT_PATTERNS {
+            rules.add(pattern)?;
+        }
+        for pattern in extra_patterns {
+            rules.add(pattern)?;
+        }
+        let ignore_file = project_dir.join(IGNORE_FILE);
+        if ignore_file.is_file() {
+            for line in fs::read_to_string(&ignore_file)?.lines() {
+                rules.add(line)?;
+            }
+        }
+        Ok(rules)
+    }
+
+    /// 添加一行规则, 空行和 `#` 开头的注释会被忽略
+    pub fn add(&mut self, line: &str) -> Result<()> {
+        let line = line.trim_end();
+        if line.trim().is_empty() || line.starts_with('#') {
+            return Ok(());
+        }
+        let (negate, pattern) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let (dir_only, pattern) = match pattern.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, pattern),
+        };
+        // 不含 `/` 的规则匹配任意层级的文件名, 其余规则相对项目根目录
+        let pattern = match pattern.strip_prefix('/') {
+            Some(rest) => rest.to_string(),
+            None if pattern.contains('/') => pattern.to_string(),
+            None => format!("**/{}", pattern),
+        };
+        self.rules.push(Rule {
+            glob: Glob::new(&pattern)?,
+            negate,
+            dir_only,
+        });
+        Ok(())
+    }
+
+    /// `path` 为相对项目根目录、以 `/` 分隔的路径
+    pub fn is_ignored(&self, path: &str, is_dir: bool) -> bool {
+        let mut ignored = false;
+        for rule in &self.rules {
+            if (!rule.dir_only || is_dir) && rule.glob.is_match(path) {
+                ignored = !rule.negate;
+            }
+        }
+        ignored
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(lines: &[&str]) -> IgnoreRules {
+        let mut rules = IgnoreRules::default();
+        for line in lines {
+            rules.add(line).unwrap();
+        }
+        rules
+    }
+
+    #[test]
+    fn pattern_without_slash_matches_at_any_depth() {
+        let rules = rules(&["*.psd", "notes.txt"]);
+        assert!(rules.is_ignored("art.psd", false));
+        assert!(rules.is_ignored("behavior_pack_1/textures/art.psd", false));
+        assert!(rules.is_ignored("resource_pack_1/notes.txt", false));
+        assert!(!rules.is_ignored("resource_pack_1/notes.txt.bak", false));
+    }
+
+    #[test]
+    fn pattern_with_slash_is_anchored_to_the_root() {
+        let rules = rules(&["art/x.png", "/top.txt", "behavior_pack_*/art/**"]);
+        assert!(rules.is_ignored("art/x.png", false));
+        assert!(!rules.is_ignored("resource_pack_1/art/x.png", false));
+        assert!(rules.is_ignored("top.txt", false));
+        assert!(!rules.is_ignored("resource_pack_1/top.txt", false));
+        assert!(rules.is_ignored("behavior_pack_1/art/a/b.png", false));
+        assert!(!rules.is_ignored("resource_pack_1/art/a/b.png", false));
+    }
+
+    #[test]
+    fn trailing_slash_only_matches_directories() {
+        let rules = rules(&["build/"]);
+        assert!(rules.is_ignored("build", true));
+        assert!(rules.is_ignored("resource_pack_1/build", true));
+        assert!(!rules.is_ignored("build", false));
+    }
+
+    #[test]
+    fn later_negation_reincludes_paths() {
+        let rules = rules(&["*.png", "!keep.png", "textures/keep.png"]);
+        assert!(rules.is_ignored("a.png", false));
+        assert!(!rules.is_ignored("keep.png", false));
+        assert!(!rules.is_ignored("blocks/keep.png", false));
+        assert!(rules.is_ignored("textures/keep.png", false));
+    }
+
+    #[test]
+    fn skips_comments_blank_lines_and_unescapes() {
+        let rules = rules(&["# comment", "", "   ", "\\#file", "\\!important"]);
+        assert!(rules.is_ignored("#file", false));
+        assert!(rules.is_ignored("!important", false));
+        assert!(!rules.is_ignored("comment", false));
+    }
+
+    #[test]
+    fn default_patterns_ignore_caches_and_editor_files() {
+        let rules = IgnoreRules::load(Path::new("/nonexistent"), &[]).unwrap();
+        assert!(rules.is_ignored("behavior_pack_1/modScripts/__pycache__", true));
+        assert!(!rules.is_ignored("behavior_pack_1/modScripts/__pycache__", false));
+        assert!(rules.is_ignored("behavior_pack_1/modScripts/modMain.pyc", false));
+        assert!(!rules.is_ignored("behavior_pack_1/modScripts/modMain.py", false));
+        assert!(rules.is_ignored(".mcs", true));
+        assert!(rules.is_ignored("resource_pack_1/.mcs", true));
+        assert!(rules.is_ignored("resource_pack_1/textures/.DS_Store", false));
+        assert!(rules.is_ignored("resource_pack_1/.git", true));
+    }
+
+    #[test]
+    fn extra_patterns_apply_after_defaults() {
+        let rules = IgnoreRules::load(Path::new("/nonexistent"), &["!*.psd".to_string()]).unwrap();
+        assert!(!rules.is_ignored("resource_pack_1/art.psd", false));
+    }
+}
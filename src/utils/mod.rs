@@ -1,3 +1,6 @@
 pub mod git;
 pub mod file;
-pub mod http;
\ No newline at end of file
+pub mod http;
+pub mod glob;
+pub mod prompt;
+pub mod ignore;
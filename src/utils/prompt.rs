@@ -0,0 +1,31 @@
+use crate::error::{CliError, Result};
+use std::io::{self, BufRead, Write};
+
+/// 读取一行输入, 去除首尾空白
+pub fn input(message: &str) -> Result<String> {
+    print!("{}: ", message);
+    io::stdout().flush()?;
+
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line)? == 0 {
+        return Err(CliError::InvalidInput(format!("未提供输入: {}", message)));
+    }
+    Ok(line.trim().to_string())
+}
+
+/// 读取一行非空输入, 为空时重新询问
+pub fn input_required(message: &str) -> Result<String> {
+    loop {
+        let value = input(message)?;
+        if !value.is_empty() {
+            return Ok(value);
+        }
+        eprintln!("该值不能为空");
+    }
+}
+
+/// 询问是否确认, 默认为否
+pub fn confirm(message: &str) -> Result<bool> {
+    let answer = input(&format!("{} [y/N]", message))?;
+    Ok(matches!(answer.to_ascii_lowercase().as_str(), "y" | "yes"))
+}